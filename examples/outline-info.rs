@@ -14,9 +14,25 @@ fn main() {
         outline.oblique(0.25);
     }
 
-    println!("bbox: {:?}", outline.bbox());
+    println!("control box: {:?}", outline.control_box());
+    println!("ink box: {:?}", outline.ink_box());
+    println!(
+        "contours: {}, points: {}, segments: {}",
+        outline.num_contours(),
+        outline.num_points(),
+        outline.num_segments()
+    );
     let mut printer = OutlinePrinter;
     outline.emit(&mut printer);
+
+    #[cfg(feature = "image")]
+    if let Some(path) = &opt.png {
+        let scale = opt.size / f32::from(face.units_per_em().unwrap_or(1000));
+        let ts = ttf_utils::Transform::from_scale(scale, scale);
+        let raster = outline.rasterize(ts, ttf_utils::FillRule::NonZero);
+        let image = raster.to_image(image::Luma([0]), image::Luma([255]));
+        image.save(path).unwrap();
+    }
 }
 
 #[derive(Debug, StructOpt)]
@@ -33,6 +49,16 @@ struct Opt {
     #[structopt(short, long)]
     oblique: bool,
 
+    /// Render the glyph into a PNG file.
+    #[cfg(feature = "image")]
+    #[structopt(long, parse(from_os_str))]
+    png: Option<std::path::PathBuf>,
+
+    /// The size of the rendered glyph in pixels per em.
+    #[cfg(feature = "image")]
+    #[structopt(long, default_value = "64")]
+    size: f32,
+
     #[structopt(name = "FONT_FILE", parse(from_os_str))]
     font_file: std::path::PathBuf,
 }
@@ -0,0 +1,332 @@
+use std::collections::HashSet;
+
+use ttf_parser::{Face, GlyphId};
+
+use crate::{BBox, FillRule, Outline, Raster, Transform};
+
+/// A way to pack glyphs into the pages of an atlas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Packing {
+    /// Rows of glyphs, which is fast and suits glyphs of similar heights.
+    Shelf,
+    /// Glyphs placed on the lowest edge of the packed area, which wastes less space.
+    Skyline,
+}
+
+/// Options for building an atlas.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AtlasOptions {
+    /// The width of each page in pixels.
+    pub page_width: u32,
+    /// The height of each page in pixels.
+    pub page_height: u32,
+    /// The empty pixels between glyphs and around the edges of the pages, which keeps
+    /// filtered samples from bleeding into neighbours.
+    pub padding: u32,
+    /// How glyphs are packed.
+    pub packing: Packing,
+}
+
+impl Default for AtlasOptions {
+    #[inline]
+    fn default() -> Self {
+        AtlasOptions {
+            page_width: 1024,
+            page_height: 1024,
+            padding: 1,
+            packing: Packing::Skyline,
+        }
+    }
+}
+
+/// A glyph rasterized into an atlas.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AtlasGlyph {
+    /// The glyph id.
+    pub glyph_id: GlyphId,
+    /// The size in pixels per em.
+    pub size: f32,
+    /// The index of the page.
+    pub page: usize,
+    /// The position and size of the glyph in the page, in pixels from the top left,
+    /// which are all 0 for glyphs without outlines.
+    pub x: u32,
+    /// See `x`.
+    pub y: u32,
+    /// See `x`.
+    pub width: u32,
+    /// See `x`.
+    pub height: u32,
+    /// The texture coordinates of the glyph, from 0 to 1 from the top left of the
+    /// page.
+    pub uv: BBox,
+    /// The position of the left edge, in pixels from the origin, see `Raster`.
+    pub left: i32,
+    /// The position of the top edge, in pixels up from the origin.
+    pub top: i32,
+    /// The horizontal advance in pixels.
+    pub advance: f32,
+}
+
+/// Texture pages of 8-bit coverage with glyphs rasterized at different sizes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Atlas {
+    /// The options the atlas was built with.
+    pub options: AtlasOptions,
+    /// The pixels of each page, row by row from the top, see `Raster`.
+    pub pages: Vec<Vec<u8>>,
+    /// The glyphs, in the order they were requested.
+    pub glyphs: Vec<AtlasGlyph>,
+}
+
+impl Atlas {
+    /// Rasterizes glyphs at sizes in pixels per em and packs them into as many pages
+    /// as needed.
+    ///
+    /// Glyphs are rasterized with `Outline::rasterize` and the non-zero fill rule.
+    /// Taller glyphs are packed first, which packs tighter. Glyphs that don't fit on a
+    /// page of their own, duplicates and sizes that aren't finite are left out.
+    pub fn new(face: &Face, glyphs: &[(GlyphId, f32)], options: AtlasOptions) -> Self {
+        let units_per_em = f32::from(face.units_per_em().unwrap_or(1000));
+        let mut entries: Vec<(AtlasGlyph, Raster)> = Vec::with_capacity(glyphs.len());
+        let mut seen = HashSet::with_capacity(glyphs.len());
+        for &(glyph_id, size) in glyphs {
+            match glyph_key(glyph_id, size) {
+                Some(key) if seen.insert(key) => {}
+                _ => continue,
+            }
+
+            let scale = size / units_per_em;
+            let raster = Outline::new(face, glyph_id)
+                .map(|outline| {
+                    outline.rasterize(Transform::from_scale(scale, scale), FillRule::NonZero)
+                })
+                .unwrap_or_default();
+            let glyph = AtlasGlyph {
+                glyph_id,
+                size,
+                page: 0,
+                x: 0,
+                y: 0,
+                width: raster.width,
+                height: raster.height,
+                uv: BBox::default(),
+                left: raster.left,
+                top: raster.top,
+                advance: face.glyph_hor_advance(glyph_id).map_or(0.0, f32::from) * scale,
+            };
+            entries.push((glyph, raster));
+        }
+
+        let mut order: Vec<usize> = (0..entries.len()).collect();
+        order.sort_by_key(|&i| std::cmp::Reverse(entries[i].0.height));
+
+        let padding = options.padding;
+        let area_width = options.page_width.saturating_sub(padding);
+        let area_height = options.page_height.saturating_sub(padding);
+        let page_len = options.page_width as usize * options.page_height as usize;
+        let mut packers: Vec<Packer> = Vec::new();
+        let mut pages: Vec<Vec<u8>> = Vec::new();
+        let mut packed = vec![false; entries.len()];
+        for i in order {
+            let (glyph, raster) = &mut entries[i];
+            if raster.pixels.is_empty() {
+                packed[i] = true;
+                continue;
+            }
+
+            let (w, h) = (glyph.width + padding, glyph.height + padding);
+            let position = packers
+                .iter_mut()
+                .enumerate()
+                .find_map(|(page, packer)| Some((page, packer.pack(w, h)?)));
+            let (page, (x, y)) = match position {
+                Some(position) => position,
+                None => {
+                    let mut packer = Packer::new(options.packing, area_width, area_height);
+                    match packer.pack(w, h) {
+                        Some(position) => {
+                            packers.push(packer);
+                            pages.push(vec![0; page_len]);
+                            (packers.len() - 1, position)
+                        }
+                        None => continue,
+                    }
+                }
+            };
+
+            glyph.page = page;
+            glyph.x = x + padding;
+            glyph.y = y + padding;
+            glyph.uv = BBox {
+                x_min: glyph.x as f32 / options.page_width as f32,
+                y_min: glyph.y as f32 / options.page_height as f32,
+                x_max: (glyph.x + glyph.width) as f32 / options.page_width as f32,
+                y_max: (glyph.y + glyph.height) as f32 / options.page_height as f32,
+            };
+            for (row, pixels) in raster.pixels.chunks(raster.width as usize).enumerate() {
+                let start =
+                    (glyph.y as usize + row) * options.page_width as usize + glyph.x as usize;
+                pages[page][start..start + pixels.len()].copy_from_slice(pixels);
+            }
+
+            packed[i] = true;
+        }
+
+        Atlas {
+            options,
+            pages,
+            glyphs: entries
+                .into_iter()
+                .zip(packed)
+                .filter(|(_, packed)| *packed)
+                .map(|((glyph, _), _)| glyph)
+                .collect(),
+        }
+    }
+
+    /// Returns the glyph at a size in pixels per em, or `None` when the atlas lacks
+    /// it.
+    pub fn glyph(&self, glyph_id: GlyphId, size: f32) -> Option<&AtlasGlyph> {
+        let key = glyph_key(glyph_id, size)?;
+        self.glyphs
+            .iter()
+            .find(|g| glyph_key(g.glyph_id, g.size) == Some(key))
+    }
+}
+
+// Returns the key of a glyph at a size, which is the same for `0.0` and `-0.0`, or
+// `None` for sizes that aren't finite.
+fn glyph_key(glyph_id: GlyphId, size: f32) -> Option<(u16, u32)> {
+    if !size.is_finite() {
+        return None;
+    }
+
+    Some((glyph_id.0, (size + 0.0).to_bits()))
+}
+
+// Places rectangles in the area of a page.
+enum Packer {
+    // The top, height and used width of each shelf.
+    Shelf {
+        width: u32,
+        height: u32,
+        shelves: Vec<(u32, u32, u32)>,
+    },
+    // The left, top and width of each segment of the skyline, from left to right.
+    Skyline {
+        width: u32,
+        height: u32,
+        segments: Vec<(u32, u32, u32)>,
+    },
+}
+
+impl Packer {
+    fn new(packing: Packing, width: u32, height: u32) -> Self {
+        match packing {
+            Packing::Shelf => Packer::Shelf {
+                width,
+                height,
+                shelves: Vec::new(),
+            },
+            Packing::Skyline => Packer::Skyline {
+                width,
+                height,
+                segments: vec![(0, 0, width)],
+            },
+        }
+    }
+
+    // Returns the top left corner of a new rectangle, or `None` when it doesn't fit.
+    fn pack(&mut self, w: u32, h: u32) -> Option<(u32, u32)> {
+        match self {
+            Packer::Shelf {
+                width,
+                height,
+                shelves,
+            } => {
+                if w > *width {
+                    return None;
+                }
+
+                for (y, shelf_height, used) in shelves.iter_mut() {
+                    if h <= *shelf_height && *used + w <= *width {
+                        let x = *used;
+                        *used += w;
+                        return Some((x, *y));
+                    }
+                }
+
+                let y = shelves
+                    .last()
+                    .map_or(0, |&(y, shelf_height, _)| y + shelf_height);
+                if y + h > *height {
+                    return None;
+                }
+
+                shelves.push((y, h, w));
+                Some((0, y))
+            }
+            Packer::Skyline {
+                width,
+                height,
+                segments,
+            } => {
+                // The segment to start at, with the top of the rectangle, which is as
+                // low as possible and then as far left as possible.
+                let mut best: Option<(usize, u32)> = None;
+                for i in 0..segments.len() {
+                    let x = segments[i].0;
+                    if x + w > *width {
+                        break;
+                    }
+
+                    let mut y = 0;
+                    let mut remaining = w;
+                    for &(_, top, segment_width) in &segments[i..] {
+                        y = y.max(top);
+                        if segment_width >= remaining {
+                            break;
+                        }
+
+                        remaining -= segment_width;
+                    }
+
+                    if y + h <= *height && !matches!(best, Some((_, best_y)) if best_y <= y) {
+                        best = Some((i, y));
+                    }
+                }
+
+                let (i, y) = best?;
+                let x = segments[i].0;
+                // Remove the segments below the rectangle and shorten the last one.
+                let mut end = i;
+                while end < segments.len() && segments[end].0 + segments[end].2 <= x + w {
+                    end += 1;
+                }
+
+                if end < segments.len() && segments[end].0 < x + w {
+                    let cut = x + w - segments[end].0;
+                    segments[end].0 += cut;
+                    segments[end].2 -= cut;
+                }
+
+                segments.splice(i..end, std::iter::once((x, y + h, w)));
+                // Merge neighbours at the same height.
+                let mut j = 0;
+                while j + 1 < segments.len() {
+                    if segments[j].1 == segments[j + 1].1 {
+                        segments[j].2 += segments[j + 1].2;
+                        segments.remove(j + 1);
+                    } else {
+                        j += 1;
+                    }
+                }
+
+                Some((x, y))
+            }
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use kurbo::PathEl;
+use ttf_parser::OutlineBuilder as _;
+
+use crate::{Outline, OutlineRecorder};
+
+impl Outline {
+    /// Returns the outline as a `kurbo::BezPath`.
+    pub fn to_bez_path(&self) -> kurbo::BezPath {
+        let mut builder = BezPathBuilder(kurbo::BezPath::new());
+        self.emit(&mut builder);
+        builder.0
+    }
+
+    /// Returns a new outline from a `kurbo::BezPath`.
+    pub fn from_bez_path(path: &kurbo::BezPath) -> Self {
+        let mut builder = OutlineRecorder::new(false);
+        for el in path.elements() {
+            match *el {
+                PathEl::MoveTo(p) => builder.move_to(p.x as f32, p.y as f32),
+                PathEl::LineTo(p) => builder.line_to(p.x as f32, p.y as f32),
+                PathEl::QuadTo(p1, p) => {
+                    builder.quad_to(p1.x as f32, p1.y as f32, p.x as f32, p.y as f32)
+                }
+                PathEl::CurveTo(p1, p2, p) => builder.curve_to(
+                    p1.x as f32,
+                    p1.y as f32,
+                    p2.x as f32,
+                    p2.y as f32,
+                    p.x as f32,
+                    p.y as f32,
+                ),
+                PathEl::ClosePath => builder.close(),
+            }
+        }
+
+        builder.finish()
+    }
+}
+
+impl From<&Outline> for kurbo::BezPath {
+    #[inline]
+    fn from(outline: &Outline) -> Self {
+        outline.to_bez_path()
+    }
+}
+
+impl From<&kurbo::BezPath> for Outline {
+    #[inline]
+    fn from(path: &kurbo::BezPath) -> Self {
+        Outline::from_bez_path(path)
+    }
+}
+
+struct BezPathBuilder(kurbo::BezPath);
+
+impl ttf_parser::OutlineBuilder for BezPathBuilder {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.0.move_to((x as f64, y as f64));
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.0.line_to((x as f64, y as f64));
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        self.0.quad_to((x1 as f64, y1 as f64), (x as f64, y as f64));
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        self.0.curve_to(
+            (x1 as f64, y1 as f64),
+            (x2 as f64, y2 as f64),
+            (x as f64, y as f64),
+        );
+    }
+
+    fn close(&mut self) {
+        self.0.close_path();
+    }
+}
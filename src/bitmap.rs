@@ -0,0 +1,236 @@
+use ttf_parser::{Face, GlyphId, Tag};
+
+use crate::parser::{read_u16, read_u32, read_u8};
+use crate::Outline;
+
+// The size of a `BitmapSize` record in the `EBLC` table.
+const BITMAP_SIZE_LEN: usize = 48;
+
+/// The format of the data of a bitmap glyph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BitmapFormat {
+    /// An encoded PNG image from the `sbix` or `CBDT` table.
+    Png,
+    /// 8-bit coverage from the `EBDT` table, row by row from the top, scaled up from
+    /// the bit depth of the strike.
+    Gray,
+}
+
+/// An embedded bitmap of a glyph from the `sbix`, `CBDT` or `EBDT` table.
+///
+/// Metrics are in pixels of the strike, not font units.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BitmapGlyph {
+    /// The horizontal offset from the origin to the left of the image.
+    pub x: i16,
+    /// The vertical offset from the baseline to the bottom of the image.
+    pub y: i16,
+    /// The image width.
+    pub width: u16,
+    /// The image height.
+    pub height: u16,
+    /// The pixels per em of the strike, which can differ from the requested size.
+    pub pixels_per_em: u16,
+    /// The format of `data`.
+    pub format: BitmapFormat,
+    /// The image data.
+    pub data: Vec<u8>,
+}
+
+impl BitmapGlyph {
+    /// Returns the bitmap from the strike closest to `pixels_per_em` or `None` when
+    /// the glyph has no bitmap.
+    ///
+    /// PNG images aren't decoded or validated. `EBDT` bitmaps are only used when the
+    /// glyph has no PNG image, and composite `EBDT` bitmaps aren't supported.
+    pub fn new(face: &Face, glyph_id: GlyphId, pixels_per_em: u16) -> Option<Self> {
+        match face.glyph_raster_image(glyph_id, pixels_per_em) {
+            Some(image) => Some(BitmapGlyph {
+                x: image.x,
+                y: image.y,
+                width: image.width,
+                height: image.height,
+                pixels_per_em: image.pixels_per_em,
+                format: BitmapFormat::Png,
+                data: image.data.to_vec(),
+            }),
+            None => ebdt_bitmap(face, glyph_id.0, pixels_per_em),
+        }
+    }
+
+    /// Returns the scale that draws the bitmap at another size.
+    #[inline]
+    pub fn scale_for(&self, pixels_per_em: f32) -> f32 {
+        pixels_per_em / self.pixels_per_em.max(1) as f32
+    }
+}
+
+// Returns a bitmap from the `EBLC` and `EBDT` tables.
+fn ebdt_bitmap(face: &Face, glyph_id: u16, pixels_per_em: u16) -> Option<BitmapGlyph> {
+    let eblc = face.table_data(Tag::from_bytes(b"EBLC"))?;
+    let ebdt = face.table_data(Tag::from_bytes(b"EBDT"))?;
+
+    // Among the strikes with the glyph, the smallest one at least as large as the
+    // requested size, or else the largest one, like for `CBDT`.
+    let mut best: Option<(usize, u16)> = None;
+    for i in 0..read_u32(eblc, 4)? as usize {
+        let size = 8 + i * BITMAP_SIZE_LEN;
+        let first = read_u16(eblc, size + 40)?;
+        let last = read_u16(eblc, size + 42)?;
+        let ppem = u16::from(read_u8(eblc, size + 44)?);
+        if !(first..=last).contains(&glyph_id) {
+            continue;
+        }
+
+        let better = match best {
+            None => true,
+            Some((_, best_ppem)) if best_ppem >= pixels_per_em => {
+                ppem >= pixels_per_em && ppem < best_ppem
+            }
+            Some((_, best_ppem)) => ppem > best_ppem,
+        };
+        if better {
+            best = Some((size, ppem));
+        }
+    }
+
+    let (size, ppem) = best?;
+    let array = read_u32(eblc, size)? as usize;
+    let bit_depth = read_u8(eblc, size + 46)?;
+    let (first, subtable) = (0..read_u32(eblc, size + 8)? as usize).find_map(|i| {
+        let record = array + i * 8;
+        let first = read_u16(eblc, record)?;
+        let last = read_u16(eblc, record + 2)?;
+        let offset = read_u32(eblc, record + 4)? as usize;
+        Some((first, array + offset)).filter(|_| (first..=last).contains(&glyph_id))
+    })?;
+
+    let index_format = read_u16(eblc, subtable)?;
+    let image_format = read_u16(eblc, subtable + 2)?;
+    let image_data = read_u32(eblc, subtable + 4)? as usize;
+    let index = usize::from(glyph_id - first);
+    // The offset and length of the glyph data, and for the index formats with glyphs
+    // of the same size, the offset of their metrics.
+    let (offset, len, metrics) = match index_format {
+        1 => {
+            let at = subtable + 8 + index * 4;
+            let start = read_u32(eblc, at)? as usize;
+            let end = read_u32(eblc, at + 4)? as usize;
+            (start, end.checked_sub(start)?, None)
+        }
+        2 => {
+            let image_size = read_u32(eblc, subtable + 8)? as usize;
+            let start = index.checked_mul(image_size)?;
+            (start, image_size, Some(subtable + 12))
+        }
+        3 => {
+            let at = subtable + 8 + index * 2;
+            let start = read_u16(eblc, at)? as usize;
+            let end = read_u16(eblc, at + 2)? as usize;
+            (start, end.checked_sub(start)?, None)
+        }
+        4 => {
+            let num_glyphs = read_u32(eblc, subtable + 8)? as usize;
+            let pair = (0..num_glyphs)
+                .map(|i| subtable + 12 + i * 4)
+                .find(|&at| read_u16(eblc, at) == Some(glyph_id))?;
+            let start = read_u16(eblc, pair + 2)? as usize;
+            let end = read_u16(eblc, pair + 6)? as usize;
+            (start, end.checked_sub(start)?, None)
+        }
+        5 => {
+            let image_size = read_u32(eblc, subtable + 8)? as usize;
+            let num_glyphs = read_u32(eblc, subtable + 20)? as usize;
+            let index = (0..num_glyphs)
+                .find(|&i| read_u16(eblc, subtable + 24 + i * 2) == Some(glyph_id))?;
+            let start = index.checked_mul(image_size)?;
+            (start, image_size, Some(subtable + 12))
+        }
+        _ => return None,
+    };
+
+    let start = image_data.checked_add(offset)?;
+    let data = ebdt.get(start..start.checked_add(len)?)?;
+    // The first four bytes of the small and big metrics are the same.
+    let (metrics, bitmap, byte_aligned) = match image_format {
+        1 | 2 => (data, data.get(5..)?, image_format == 1),
+        5 => (eblc.get(metrics?..)?, data, false),
+        6 | 7 => (data, data.get(8..)?, image_format == 6),
+        // Composite bitmaps and unknown formats.
+        _ => return None,
+    };
+
+    let height = read_u8(metrics, 0)?;
+    let width = read_u8(metrics, 1)?;
+    let x = read_u8(metrics, 2)? as i8;
+    let y = read_u8(metrics, 3)? as i8;
+    Some(BitmapGlyph {
+        x: i16::from(x),
+        y: i16::from(y) - i16::from(height),
+        width: u16::from(width),
+        height: u16::from(height),
+        pixels_per_em: ppem,
+        format: BitmapFormat::Gray,
+        data: expand(bitmap, width.into(), height.into(), bit_depth, byte_aligned)?,
+    })
+}
+
+// Returns 8-bit pixels of an image with 1, 2, 4 or 8 bits per pixel, where rows are
+// either padded to whole bytes or packed.
+fn expand(
+    bitmap: &[u8],
+    width: usize,
+    height: usize,
+    bit_depth: u8,
+    byte_aligned: bool,
+) -> Option<Vec<u8>> {
+    if !matches!(bit_depth, 1 | 2 | 4 | 8) {
+        return None;
+    }
+
+    let depth = usize::from(bit_depth);
+    let max = (1u32 << depth) - 1;
+    // The bits per row.
+    let stride = if byte_aligned {
+        (width * depth).div_ceil(8) * 8
+    } else {
+        width * depth
+    };
+
+    let mut pixels = Vec::with_capacity(width * height);
+    for row in 0..height {
+        for column in 0..width {
+            // Pixels never straddle bytes since the depth divides eight.
+            let bit = row * stride + column * depth;
+            let value = u32::from(*bitmap.get(bit / 8)? >> (8 - depth - bit % 8)) & max;
+            pixels.push((value * 255 / max) as u8);
+        }
+    }
+
+    Some(pixels)
+}
+
+/// Something drawable for a glyph.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GlyphImage {
+    /// An embedded bitmap.
+    Bitmap(BitmapGlyph),
+    /// An outline.
+    Outline(Outline),
+}
+
+impl GlyphImage {
+    /// Returns the embedded bitmap of a glyph, falling back to its outline, or `None`
+    /// when it has neither.
+    ///
+    /// Bitmaps come first since fonts that have both, like color emoji fonts, often
+    /// only have placeholder outlines.
+    pub fn new(face: &Face, glyph_id: GlyphId, pixels_per_em: u16) -> Option<Self> {
+        BitmapGlyph::new(face, glyph_id, pixels_per_em)
+            .map(GlyphImage::Bitmap)
+            .or_else(|| Outline::new(face, glyph_id).map(GlyphImage::Outline))
+    }
+}
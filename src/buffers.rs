@@ -0,0 +1,59 @@
+use crate::Outline;
+
+/// Flat outline buffers, suitable for direct GPU upload.
+///
+/// Verbs consume points in the same way as `Outline::emit`: one point for move and
+/// line verbs, two for quadratic and three for cubic curves, and none for close.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct OutlineBuffers {
+    /// Interleaved `x, y` point coordinates.
+    pub points: Vec<f32>,
+    /// Path verbs, see the `VERB_*` constants.
+    pub verbs: Vec<u8>,
+    /// The end index (exclusive) in `verbs` of each contour.
+    pub contour_verb_ends: Vec<u32>,
+    /// The end index (exclusive) in `points`, counted in points, of each contour.
+    pub contour_point_ends: Vec<u32>,
+}
+
+impl OutlineBuffers {
+    /// Move verb.
+    pub const VERB_MOVE_TO: u8 = 0;
+    /// Line verb.
+    pub const VERB_LINE_TO: u8 = 1;
+    /// Quadratic curve verb.
+    pub const VERB_QUAD_TO: u8 = 2;
+    /// Cubic curve verb.
+    pub const VERB_CURVE_TO: u8 = 3;
+    /// Close verb.
+    pub const VERB_CLOSE: u8 = 4;
+}
+
+impl Outline {
+    /// Returns the outline as flat buffers.
+    pub fn to_buffers(&self) -> OutlineBuffers {
+        let num_verbs = self.contours.iter().map(|c| c.verbs.len()).sum();
+        let num_points: usize = self.contours.iter().map(|c| c.points.len()).sum();
+        let mut buffers = OutlineBuffers {
+            points: Vec::with_capacity(num_points * 2),
+            verbs: Vec::with_capacity(num_verbs),
+            contour_verb_ends: Vec::with_capacity(self.contours.len()),
+            contour_point_ends: Vec::with_capacity(self.contours.len()),
+        };
+        for c in &self.contours {
+            // The verb discriminants match the `VERB_*` constants.
+            buffers.verbs.extend(c.verbs.iter().map(|v| *v as u8));
+            for p in &c.points {
+                buffers.points.push(p.x);
+                buffers.points.push(p.y);
+            }
+
+            buffers.contour_verb_ends.push(buffers.verbs.len() as u32);
+            buffers
+                .contour_point_ends
+                .push((buffers.points.len() / 2) as u32);
+        }
+
+        buffers
+    }
+}
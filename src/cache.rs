@@ -0,0 +1,117 @@
+use std::io::Write;
+
+use crate::{Contour, Outline, PathVerb, Point};
+
+const MAGIC: &[u8; 4] = b"TTFO";
+const VERSION: u8 = 1;
+const FLAG_CFF: u8 = 0x01;
+
+impl Outline {
+    /// Writes the outline in a compact binary format.
+    ///
+    /// The format starts with a magic number and a version, followed by the contours
+    /// where verbs are stored as bytes and points as little-endian `f32` pairs. Use
+    /// `read_from` to read it back.
+    pub fn write_to<W: Write>(&self, w: &mut W) -> std::io::Result<()> {
+        w.write_all(MAGIC)?;
+        w.write_all(&[VERSION, if self.cff { FLAG_CFF } else { 0 }])?;
+        w.write_all(&(self.contours.len() as u32).to_le_bytes())?;
+        for c in &self.contours {
+            w.write_all(&(c.verbs.len() as u32).to_le_bytes())?;
+            let verbs: Vec<u8> = c.verbs.iter().map(|v| *v as u8).collect();
+            w.write_all(&verbs)?;
+            for p in &c.points {
+                w.write_all(&p.x.to_le_bytes())?;
+                w.write_all(&p.y.to_le_bytes())?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reads an outline written by `write_to`.
+    ///
+    /// Returns `None` when the data is malformed, truncated or has an unknown version.
+    pub fn read_from(data: &[u8]) -> Option<Self> {
+        let mut r = Reader(data);
+        if r.read(4)? != MAGIC || r.read_u8()? != VERSION {
+            return None;
+        }
+
+        let flags = r.read_u8()?;
+        let num_contours = r.read_u32()? as usize;
+        // Every contour needs at least four bytes, so don't trust larger counts.
+        let mut contours = Vec::with_capacity(num_contours.min(r.0.len() / 4));
+        for _ in 0..num_contours {
+            let num_verbs = r.read_u32()? as usize;
+            let verbs = r
+                .read(num_verbs)?
+                .iter()
+                .map(|v| PathVerb::from_u8(*v))
+                .collect::<Option<Vec<_>>>()?;
+            let num_points = verbs.iter().map(|v| v.num_points()).sum();
+            let mut points = Vec::with_capacity(num_points);
+            for _ in 0..num_points {
+                points.push(Point::new(r.read_f32()?, r.read_f32()?));
+            }
+
+            contours.push(Contour::from_parts(verbs, points, contours.len())?);
+        }
+
+        if !r.0.is_empty() {
+            return None;
+        }
+
+        let mut outline = Outline::empty(flags & FLAG_CFF != 0);
+        outline.contours = contours;
+        Some(outline)
+    }
+}
+
+impl PathVerb {
+    #[inline]
+    fn from_u8(n: u8) -> Option<Self> {
+        match n {
+            0 => Some(PathVerb::MoveTo),
+            1 => Some(PathVerb::LineTo),
+            2 => Some(PathVerb::QuadTo),
+            3 => Some(PathVerb::CurveTo),
+            4 => Some(PathVerb::Close),
+            _ => None,
+        }
+    }
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    #[inline]
+    fn read(&mut self, len: usize) -> Option<&'a [u8]> {
+        if len > self.0.len() {
+            return None;
+        }
+
+        let (data, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Some(data)
+    }
+
+    #[inline]
+    fn read_u8(&mut self) -> Option<u8> {
+        Some(self.read(1)?[0])
+    }
+
+    #[inline]
+    fn read_u32(&mut self) -> Option<u32> {
+        let mut bytes = [0; 4];
+        bytes.copy_from_slice(self.read(4)?);
+        Some(u32::from_le_bytes(bytes))
+    }
+
+    #[inline]
+    fn read_f32(&mut self) -> Option<f32> {
+        let mut bytes = [0; 4];
+        bytes.copy_from_slice(self.read(4)?);
+        Some(f32::from_le_bytes(bytes))
+    }
+}
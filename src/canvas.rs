@@ -0,0 +1,63 @@
+use crate::{write_number, Outline, Transform};
+
+impl Outline {
+    /// Returns the outline as HTML canvas drawing commands.
+    ///
+    /// Emits `moveTo`, `lineTo`, `quadraticCurveTo`, `bezierCurveTo` and `closePath`
+    /// calls on the `ctx` variable, one per line, with `ts` applied to each point.
+    /// Canvas is y-down, so pass e.g. `Transform::from_scale(s, -s)` to flip and scale
+    /// the glyph. For a `Path2D`, pass `to_svg_path` data to its constructor instead.
+    pub fn to_canvas_commands(&self, ctx: &str, ts: Transform) -> String {
+        let mut writer = CanvasWriter {
+            data: String::new(),
+            ctx,
+        };
+        self.emit_transformed(ts, &mut writer);
+        writer.data
+    }
+}
+
+struct CanvasWriter<'a> {
+    data: String,
+    ctx: &'a str,
+}
+
+impl CanvasWriter<'_> {
+    fn push(&mut self, method: &str, coords: &[f32]) {
+        self.data.push_str(self.ctx);
+        self.data.push('.');
+        self.data.push_str(method);
+        self.data.push('(');
+        for (i, n) in coords.iter().enumerate() {
+            if i > 0 {
+                self.data.push_str(", ");
+            }
+
+            write_number(&mut self.data, *n, None);
+        }
+
+        self.data.push_str(");\n");
+    }
+}
+
+impl ttf_parser::OutlineBuilder for CanvasWriter<'_> {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.push("moveTo", &[x, y]);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.push("lineTo", &[x, y]);
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        self.push("quadraticCurveTo", &[x1, y1, x, y]);
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        self.push("bezierCurveTo", &[x1, y1, x2, y2, x, y]);
+    }
+
+    fn close(&mut self) {
+        self.push("closePath", &[]);
+    }
+}
@@ -0,0 +1,174 @@
+use crate::Outline;
+
+const RLINETO: u8 = 5;
+const RRCURVETO: u8 = 8;
+const ENDCHAR: u8 = 14;
+const RMOVETO: u8 = 21;
+
+// The Type 2 argument stack limit, which is well within the CFF2 limit as well.
+const MAX_ARGS: usize = 48;
+
+impl Outline {
+    /// Returns the outline as a Type 2 (CFF) charstring.
+    ///
+    /// The charstring uses relative operators only and contains no hints. Quadratic
+    /// curves are converted to cubic ones. When present, `width` is encoded as the
+    /// difference from the font's `nominalWidthX`, as required by CFF.
+    ///
+    /// Returns `None` when a number can't be encoded, i.e. when the width or the
+    /// difference between two points isn't finite or is beyond ±32768.
+    pub fn to_type2_charstring(&self, width: Option<f32>) -> Option<Vec<u8>> {
+        let mut writer = CharstringWriter::new();
+        if let Some(width) = width {
+            writer.args.push(width);
+        }
+
+        self.emit(&mut writer);
+        writer.flush();
+        // Writes the width as well when the outline is empty.
+        writer.flush_op(ENDCHAR);
+        writer.finish()
+    }
+
+    /// Returns the outline as a CFF2 charstring.
+    ///
+    /// Same as `to_type2_charstring`, but without a width and without the trailing
+    /// `endchar` operator, both of which were removed in CFF2. No blend operators are
+    /// emitted, i.e. the charstring describes a single instance.
+    pub fn to_cff2_charstring(&self) -> Option<Vec<u8>> {
+        let mut writer = CharstringWriter::new();
+        self.emit(&mut writer);
+        writer.flush();
+        writer.finish()
+    }
+}
+
+struct CharstringWriter {
+    data: Vec<u8>,
+    args: Vec<f32>,
+    op: Option<u8>,
+    last: (f32, f32),
+    overflow: bool,
+}
+
+impl CharstringWriter {
+    fn new() -> Self {
+        Self {
+            data: Vec::new(),
+            args: Vec::new(),
+            op: None,
+            last: (0.0, 0.0),
+            overflow: false,
+        }
+    }
+
+    fn finish(self) -> Option<Vec<u8>> {
+        if self.overflow {
+            return None;
+        }
+
+        Some(self.data)
+    }
+
+    fn push(&mut self, op: u8, points: &[(f32, f32)]) {
+        if self.op != Some(op) || self.args.len() + points.len() * 2 > MAX_ARGS {
+            self.flush();
+        }
+
+        for &(x, y) in points {
+            self.args.push(x - self.last.0);
+            self.args.push(y - self.last.1);
+            self.last = (x, y);
+        }
+
+        self.op = Some(op);
+    }
+
+    fn flush(&mut self) {
+        if let Some(op) = self.op.take() {
+            self.flush_op(op);
+        }
+    }
+
+    fn flush_op(&mut self, op: u8) {
+        for n in &self.args {
+            if write_number(&mut self.data, *n).is_none() {
+                self.overflow = true;
+            }
+        }
+
+        self.args.clear();
+        self.data.push(op);
+    }
+}
+
+impl ttf_parser::OutlineBuilder for CharstringWriter {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.flush();
+        // A pending width stays on the stack and becomes the first `rmoveto` argument.
+        self.args.push(x - self.last.0);
+        self.args.push(y - self.last.1);
+        self.last = (x, y);
+        self.flush_op(RMOVETO);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.push(RLINETO, &[(x, y)]);
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let (x0, y0) = self.last;
+        self.push(
+            RRCURVETO,
+            &[
+                (x0 + 2.0 / 3.0 * (x1 - x0), y0 + 2.0 / 3.0 * (y1 - y0)),
+                (x + 2.0 / 3.0 * (x1 - x), y + 2.0 / 3.0 * (y1 - y)),
+                (x, y),
+            ],
+        );
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        self.push(RRCURVETO, &[(x1, y1), (x2, y2), (x, y)]);
+    }
+
+    fn close(&mut self) {
+        // Contours are implicitly closed in charstrings.
+    }
+}
+
+// Returns `None` when the number is out of the range of 16.16 fixed.
+fn write_number(data: &mut Vec<u8>, n: f32) -> Option<()> {
+    if n.fract() != 0.0 || !(-32768.0..=32767.0).contains(&n) {
+        let v = (n * 65536.0).round();
+        // Also false for NaN.
+        if !(v >= i32::MIN as f32 && v < -(i32::MIN as f32)) {
+            return None;
+        }
+
+        data.push(255);
+        data.extend_from_slice(&(v as i32).to_be_bytes());
+        return Some(());
+    }
+
+    let v = n as i32;
+    match v {
+        -107..=107 => data.push((v + 139) as u8),
+        108..=1131 => {
+            let v = v - 108;
+            data.push(((v >> 8) + 247) as u8);
+            data.push(v as u8);
+        }
+        -1131..=-108 => {
+            let v = -v - 108;
+            data.push(((v >> 8) + 251) as u8);
+            data.push(v as u8);
+        }
+        _ => {
+            data.push(28);
+            data.extend_from_slice(&(v as i16).to_be_bytes());
+        }
+    }
+
+    Some(())
+}
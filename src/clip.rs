@@ -0,0 +1,113 @@
+use crate::geom::Segment;
+use crate::{BBox, Outline, Point};
+
+impl Outline {
+    /// Clips the outline to a rectangle.
+    ///
+    /// Contours are treated as closed and curves are split exactly where they cross
+    /// the rectangle, so the result keeps its curves. Contours that leave and enter
+    /// the rectangle again are connected along its edges, which doesn't change how
+    /// they fill.
+    pub fn clip_to(&mut self, rect: BBox) {
+        self.invalidate_bounds();
+        self.components.clear();
+        let contours = std::mem::take(&mut self.contours);
+        for mut c in contours {
+            let mut segments: Vec<Segment> = c.segments().chain(c.closing_line()).collect();
+            for &(vertical, value, min) in &[
+                (true, rect.x_min, true),
+                (true, rect.x_max, false),
+                (false, rect.y_min, true),
+                (false, rect.y_max, false),
+            ] {
+                segments = clip_segments(&segments, vertical, value, min);
+            }
+
+            let start = match segments.first() {
+                Some(segment) => segment.start(),
+                None => continue,
+            };
+
+            c.set_segments(start, segments, true);
+            self.contours.push(c);
+        }
+    }
+}
+
+// Clips a closed loop of segments to the half plane on one side of a vertical or
+// horizontal line, keeping the side with larger values unless `min` is `false`.
+fn clip_segments(segments: &[Segment], vertical: bool, value: f32, min: bool) -> Vec<Segment> {
+    let coord = |p: Point| if vertical { p.x } else { p.y };
+    let snap = |mut p: Point| {
+        if vertical {
+            p.x = value;
+        } else {
+            p.y = value;
+        }
+
+        p
+    };
+    let inside = |p: Point| {
+        if min {
+            coord(p) >= value
+        } else {
+            coord(p) <= value
+        }
+    };
+
+    let mut out: Vec<Segment> = Vec::new();
+    let mut push = |segment: Segment| {
+        if let Some(last) = out.last() {
+            if last.end() != segment.start() {
+                // Connect along the clip line.
+                out.push(Segment::Line(last.end(), segment.start()));
+            }
+        }
+
+        out.push(segment);
+    };
+    for segment in segments {
+        let mut ts = vec![0.0];
+        segment.for_each_crossing(vertical, value, |t| ts.push(t));
+        ts.push(1.0);
+        for (i, w) in ts.windows(2).enumerate() {
+            let piece = segment.subsegment(w[0], w[1]);
+            if !inside(piece.eval(0.5)) {
+                continue;
+            }
+
+            // End points at crossings are moved exactly onto the line.
+            let piece = with_end_points(
+                piece,
+                if i > 0 {
+                    snap(piece.start())
+                } else {
+                    piece.start()
+                },
+                if i + 2 < ts.len() {
+                    snap(piece.end())
+                } else {
+                    piece.end()
+                },
+            );
+            push(piece);
+        }
+    }
+
+    if let (Some(first), Some(last)) = (out.first(), out.last()) {
+        if last.end() != first.start() {
+            let line = Segment::Line(last.end(), first.start());
+            out.push(line);
+        }
+    }
+
+    out
+}
+
+fn with_end_points(segment: Segment, start: Point, end: Point) -> Segment {
+    match segment {
+        Segment::Line(..) => Segment::Line(start, end),
+        Segment::Quad(_, p1, _) => Segment::Quad(start, p1, end),
+        Segment::Cubic(_, p1, p2, _) => Segment::Cubic(start, p1, p2, end),
+    }
+}
@@ -0,0 +1,516 @@
+use std::cell::{Cell, RefCell};
+use std::collections::HashSet;
+
+use ttf_parser::{Face, GlyphId, Tag};
+
+use crate::parser::{read_f2dot14, read_fixed, read_i16, read_u16, read_u24, read_u32, read_u8};
+use crate::{Outline, Transform};
+
+// Paints nested deeper than this are treated as malformed.
+const MAX_DEPTH: usize = 64;
+
+// The most paints visited for a glyph, which bounds the work for paints reached many
+// times over, like layers that all refer to the same paint.
+const MAX_PAINTS: usize = 65536;
+
+// The palette index that stands for the text color.
+const FOREGROUND: u16 = 0xFFFF;
+
+/// An RGBA color.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Color {
+    /// Red.
+    pub r: u8,
+    /// Green.
+    pub g: u8,
+    /// Blue.
+    pub b: u8,
+    /// Alpha, not premultiplied.
+    pub a: u8,
+}
+
+impl Color {
+    /// Returns a new color.
+    #[inline]
+    pub fn new(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self { r, g, b, a }
+    }
+}
+
+/// A color at a position along a gradient.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ColorStop {
+    /// The position, where zero is the start and one the end of the gradient.
+    pub offset: f32,
+    /// The color.
+    pub color: Color,
+}
+
+/// How a gradient continues past its first and last stops.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Extend {
+    /// The colors of the first and last stops continue.
+    Pad,
+    /// The gradient repeats.
+    Repeat,
+    /// The gradient repeats, mirrored every other time.
+    Reflect,
+}
+
+/// How a source paint is combined with a backdrop, see the `COLR` specification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CompositeMode {
+    Clear,
+    Source,
+    Destination,
+    SourceOver,
+    DestinationOver,
+    SourceIn,
+    DestinationIn,
+    SourceOut,
+    DestinationOut,
+    SourceAtop,
+    DestinationAtop,
+    Xor,
+    Plus,
+    Screen,
+    Overlay,
+    Darken,
+    Lighten,
+    ColorDodge,
+    ColorBurn,
+    HardLight,
+    SoftLight,
+    Difference,
+    Exclusion,
+    Multiply,
+    Hue,
+    Saturation,
+    Color,
+    Luminosity,
+}
+
+/// A node in the paint tree of a color glyph.
+///
+/// Coordinates are in font units with the y-axis pointing up, like outlines.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Paint {
+    /// Paints drawn on top of each other, from the bottom up.
+    Layers(Vec<Paint>),
+    /// A solid color.
+    Solid(Color),
+    /// A linear gradient from `p0` to `p1`, rotated towards `p2`.
+    LinearGradient {
+        /// The start point.
+        p0: (f32, f32),
+        /// The end point.
+        p1: (f32, f32),
+        /// The rotation point, see the `COLR` specification.
+        p2: (f32, f32),
+        /// The color stops.
+        stops: Vec<ColorStop>,
+        /// How the gradient continues past its ends.
+        extend: Extend,
+    },
+    /// A radial gradient between two circles.
+    RadialGradient {
+        /// The center of the start circle.
+        c0: (f32, f32),
+        /// The radius of the start circle.
+        r0: f32,
+        /// The center of the end circle.
+        c1: (f32, f32),
+        /// The radius of the end circle.
+        r1: f32,
+        /// The color stops.
+        stops: Vec<ColorStop>,
+        /// How the gradient continues past its ends.
+        extend: Extend,
+    },
+    /// A sweep gradient around a center, with counter-clockwise angles in degrees.
+    SweepGradient {
+        /// The center.
+        center: (f32, f32),
+        /// The start angle.
+        start_angle: f32,
+        /// The end angle.
+        end_angle: f32,
+        /// The color stops.
+        stops: Vec<ColorStop>,
+        /// How the gradient continues past its ends.
+        extend: Extend,
+    },
+    /// A paint clipped to the outline of a glyph.
+    Glyph {
+        /// The clip outline.
+        outline: Outline,
+        /// The paint within the outline.
+        paint: Box<Paint>,
+    },
+    /// A transformed paint.
+    Transform {
+        /// The transform.
+        transform: Transform,
+        /// The transformed paint.
+        paint: Box<Paint>,
+    },
+    /// A paint composited onto another one.
+    Composite {
+        /// The paint drawn on top.
+        source: Box<Paint>,
+        /// How the paints are combined.
+        mode: CompositeMode,
+        /// The paint below.
+        backdrop: Box<Paint>,
+    },
+}
+
+impl Paint {
+    /// Returns the paint tree of a color glyph or `None` when the face has no color
+    /// version of the glyph or on error.
+    ///
+    /// Both `COLR` versions are supported. Version 0 glyphs become layers of solid
+    /// colored glyphs. Variable version 1 paints aren't supported, and the layers
+    /// containing them are left out. Colors come from the given `CPAL` palette, and
+    /// `foreground` is used where the font asks for the text color. Cyclic paints and
+    /// paint trees with too many paints are treated as errors.
+    pub fn new(face: &Face, glyph_id: GlyphId, palette: u16, foreground: Color) -> Option<Self> {
+        let colr = Colr::new(face, palette, foreground)?;
+        let paint = colr.base_paint(glyph_id.0, 0);
+        if colr.visited.get() > MAX_PAINTS {
+            return None;
+        }
+
+        paint.or_else(|| colr.base_layers(glyph_id.0))
+    }
+}
+
+struct Colr<'a> {
+    face: &'a Face<'a>,
+    data: &'a [u8],
+    cpal: &'a [u8],
+    // The offset of the first color record of the palette.
+    palette: usize,
+    num_entries: u16,
+    foreground: Color,
+    // The number of paints visited so far.
+    visited: Cell<usize>,
+    // The offsets of the paints being read, from the root down.
+    path: RefCell<HashSet<usize>>,
+}
+
+impl<'a> Colr<'a> {
+    fn new(face: &'a Face<'a>, palette: u16, foreground: Color) -> Option<Self> {
+        let data = face.table_data(Tag::from_bytes(b"COLR"))?;
+        let cpal = face.table_data(Tag::from_bytes(b"CPAL"))?;
+        if palette >= read_u16(cpal, 4)? {
+            return None;
+        }
+
+        let records = read_u32(cpal, 8)? as usize;
+        let first = read_u16(cpal, 12 + palette as usize * 2)? as usize;
+        Some(Colr {
+            face,
+            data,
+            cpal,
+            palette: records + first * 4,
+            num_entries: read_u16(cpal, 2)?,
+            foreground,
+            visited: Cell::new(0),
+            path: RefCell::new(HashSet::new()),
+        })
+    }
+
+    fn color(&self, index: u16, alpha: f32) -> Option<Color> {
+        let mut color = if index == FOREGROUND {
+            self.foreground
+        } else if index >= self.num_entries {
+            return None;
+        } else {
+            // The records are stored as BGRA.
+            let offset = self.palette + index as usize * 4;
+            let bgra = self.cpal.get(offset..offset + 4)?;
+            Color::new(bgra[2], bgra[1], bgra[0], bgra[3])
+        };
+
+        color.a = (color.a as f32 * alpha.clamp(0.0, 1.0)).round() as u8;
+        Some(color)
+    }
+
+    // Returns the layers of a version 0 glyph.
+    fn base_layers(&self, glyph_id: u16) -> Option<Paint> {
+        let count = read_u16(self.data, 2)? as usize;
+        let records = read_u32(self.data, 4)? as usize;
+        let layers = read_u32(self.data, 8)? as usize;
+        let record = binary_search(count, |i| read_u16(self.data, records + i * 6), glyph_id)?;
+        let record = records + record * 6;
+        let first = read_u16(self.data, record + 2)? as usize;
+        let num_layers = read_u16(self.data, record + 4)? as usize;
+
+        let paints = (first..first + num_layers)
+            .map(|i| {
+                let layer = layers + i * 4;
+                let glyph_id = read_u16(self.data, layer)?;
+                let color = self.color(read_u16(self.data, layer + 2)?, 1.0)?;
+                Some(Paint::Glyph {
+                    outline: self.outline(glyph_id),
+                    paint: Box::new(Paint::Solid(color)),
+                })
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some(Paint::Layers(paints))
+    }
+
+    // Returns the paint of a version 1 glyph.
+    fn base_paint(&self, glyph_id: u16, depth: usize) -> Option<Paint> {
+        if read_u16(self.data, 0)? < 1 {
+            return None;
+        }
+
+        let list = read_u32(self.data, 14)? as usize;
+        if list == 0 {
+            return None;
+        }
+
+        let count = read_u32(self.data, list)? as usize;
+        let record = binary_search(count, |i| read_u16(self.data, list + 4 + i * 6), glyph_id)?;
+        let paint = read_u32(self.data, list + 4 + record * 6 + 2)? as usize;
+        self.paint(list + paint, depth)
+    }
+
+    fn paint(&self, offset: usize, depth: usize) -> Option<Paint> {
+        self.visited.set(self.visited.get() + 1);
+        if depth > MAX_DEPTH
+            || self.visited.get() > MAX_PAINTS
+            || !self.path.borrow_mut().insert(offset)
+        {
+            return None;
+        }
+
+        let paint = self.read_paint(offset, depth);
+        self.path.borrow_mut().remove(&offset);
+        paint
+    }
+
+    fn read_paint(&self, offset: usize, depth: usize) -> Option<Paint> {
+        let data = self.data;
+        let child = |at: usize| -> Option<Box<Paint>> {
+            let child = offset + read_u24(data, offset + at)? as usize;
+            self.paint(child, depth + 1).map(Box::new)
+        };
+        let point = |at: usize| -> Option<(f32, f32)> {
+            Some((
+                read_i16(data, offset + at)? as f32,
+                read_i16(data, offset + at + 2)? as f32,
+            ))
+        };
+        let f2dot14 = |at: usize| read_f2dot14(data, offset + at);
+        // Angles are in half turns.
+        let angle = |at: usize| Some(f2dot14(at)? * 180.0);
+        let transformed = |transform: Transform| -> Option<Paint> {
+            Some(Paint::Transform {
+                transform,
+                paint: child(1)?,
+            })
+        };
+        let around = |transform: Transform, at: usize| -> Option<Paint> {
+            let (cx, cy) = point(at)?;
+            transformed(
+                Transform::from_translate(-cx, -cy)
+                    .then(&transform)
+                    .then(&Transform::from_translate(cx, cy)),
+            )
+        };
+        let rotate = |degrees: f32| Transform::from_rotate(degrees.to_radians());
+        let skew = |x: f32, y: f32| {
+            Transform::new(
+                1.0,
+                y.to_radians().tan(),
+                -x.to_radians().tan(),
+                1.0,
+                0.0,
+                0.0,
+            )
+        };
+
+        match read_u8(data, offset)? {
+            1 => {
+                let num_layers = read_u8(data, offset + 1)? as usize;
+                let first = read_u32(data, offset + 2)? as usize;
+                let list = read_u32(data, 18)? as usize;
+                let layers = (first..first + num_layers)
+                    .filter_map(|i| {
+                        let layer = read_u32(data, list + 4 + i * 4)? as usize;
+                        self.paint(list + layer, depth + 1)
+                    })
+                    .collect();
+                Some(Paint::Layers(layers))
+            }
+            2 => Some(Paint::Solid(
+                self.color(read_u16(data, offset + 1)?, f2dot14(3)?)?,
+            )),
+            4 => {
+                let (stops, extend) =
+                    self.color_line(offset + read_u24(data, offset + 1)? as usize)?;
+                Some(Paint::LinearGradient {
+                    p0: point(4)?,
+                    p1: point(8)?,
+                    p2: point(12)?,
+                    stops,
+                    extend,
+                })
+            }
+            6 => {
+                let (stops, extend) =
+                    self.color_line(offset + read_u24(data, offset + 1)? as usize)?;
+                Some(Paint::RadialGradient {
+                    c0: point(4)?,
+                    r0: read_u16(data, offset + 8)? as f32,
+                    c1: point(10)?,
+                    r1: read_u16(data, offset + 14)? as f32,
+                    stops,
+                    extend,
+                })
+            }
+            8 => {
+                let (stops, extend) =
+                    self.color_line(offset + read_u24(data, offset + 1)? as usize)?;
+                Some(Paint::SweepGradient {
+                    center: point(4)?,
+                    start_angle: angle(8)?,
+                    end_angle: angle(10)?,
+                    stops,
+                    extend,
+                })
+            }
+            10 => Some(Paint::Glyph {
+                outline: self.outline(read_u16(data, offset + 4)?),
+                paint: child(1)?,
+            }),
+            11 => self.base_paint(read_u16(data, offset + 1)?, depth + 1),
+            12 => {
+                let at = offset + read_u24(data, offset + 4)? as usize;
+                let fixed = |i: usize| read_fixed(data, at + i * 4);
+                transformed(Transform::new(
+                    fixed(0)?,
+                    fixed(1)?,
+                    fixed(2)?,
+                    fixed(3)?,
+                    fixed(4)?,
+                    fixed(5)?,
+                ))
+            }
+            14 => {
+                let (dx, dy) = point(4)?;
+                transformed(Transform::from_translate(dx, dy))
+            }
+            16 => transformed(Transform::from_scale(f2dot14(4)?, f2dot14(6)?)),
+            18 => around(Transform::from_scale(f2dot14(4)?, f2dot14(6)?), 8),
+            20 => transformed(Transform::from_scale(f2dot14(4)?, f2dot14(4)?)),
+            22 => around(Transform::from_scale(f2dot14(4)?, f2dot14(4)?), 6),
+            24 => transformed(rotate(angle(4)?)),
+            26 => around(rotate(angle(4)?), 6),
+            28 => transformed(skew(angle(4)?, angle(6)?)),
+            30 => around(skew(angle(4)?, angle(6)?), 8),
+            32 => {
+                let backdrop = offset + read_u24(data, offset + 5)? as usize;
+                Some(Paint::Composite {
+                    source: child(1)?,
+                    mode: composite_mode(read_u8(data, offset + 4)?)?,
+                    backdrop: Box::new(self.paint(backdrop, depth + 1)?),
+                })
+            }
+            // Variable paints and unknown formats.
+            _ => None,
+        }
+    }
+
+    fn color_line(&self, offset: usize) -> Option<(Vec<ColorStop>, Extend)> {
+        let extend = match read_u8(self.data, offset)? {
+            1 => Extend::Repeat,
+            2 => Extend::Reflect,
+            _ => Extend::Pad,
+        };
+
+        let count = read_u16(self.data, offset + 1)? as usize;
+        let stops = (0..count)
+            .map(|i| {
+                let stop = offset + 3 + i * 6;
+                Some(ColorStop {
+                    offset: read_f2dot14(self.data, stop)?,
+                    color: self.color(
+                        read_u16(self.data, stop + 2)?,
+                        read_f2dot14(self.data, stop + 4)?,
+                    )?,
+                })
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some((stops, extend))
+    }
+
+    // Glyphs without an outline clip everything away.
+    fn outline(&self, glyph_id: u16) -> Outline {
+        Outline::new(self.face, GlyphId(glyph_id)).unwrap_or_default()
+    }
+}
+
+// Returns the index of the record with the given glyph ID among sorted records.
+fn binary_search<F>(count: usize, glyph_id_at: F, glyph_id: u16) -> Option<usize>
+where
+    F: Fn(usize) -> Option<u16>,
+{
+    let (mut lo, mut hi) = (0, count);
+    while lo < hi {
+        let mid = (lo + hi) / 2;
+        let id = glyph_id_at(mid)?;
+        if id == glyph_id {
+            return Some(mid);
+        } else if id < glyph_id {
+            lo = mid + 1;
+        } else {
+            hi = mid;
+        }
+    }
+
+    None
+}
+
+fn composite_mode(mode: u8) -> Option<CompositeMode> {
+    use CompositeMode::*;
+    const MODES: [CompositeMode; 28] = [
+        Clear,
+        Source,
+        Destination,
+        SourceOver,
+        DestinationOver,
+        SourceIn,
+        DestinationIn,
+        SourceOut,
+        DestinationOut,
+        SourceAtop,
+        DestinationAtop,
+        Xor,
+        Plus,
+        Screen,
+        Overlay,
+        Darken,
+        Lighten,
+        ColorDodge,
+        ColorBurn,
+        HardLight,
+        SoftLight,
+        Difference,
+        Exclusion,
+        Multiply,
+        Hue,
+        Saturation,
+        Color,
+        Luminosity,
+    ];
+    MODES.get(mode as usize).copied()
+}
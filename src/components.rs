@@ -0,0 +1,317 @@
+use std::ops::Range;
+
+use ttf_parser::{Face, GlyphId, Tag};
+
+use crate::parser::{read_f2dot14, read_i16, read_u16, read_u32, read_u8};
+use crate::{Contour, Outline, Transform};
+
+pub(crate) const ARG_1_AND_2_ARE_WORDS: u16 = 0x0001;
+pub(crate) const ARGS_ARE_XY_VALUES: u16 = 0x0002;
+pub(crate) const WE_HAVE_A_SCALE: u16 = 0x0008;
+pub(crate) const MORE_COMPONENTS: u16 = 0x0020;
+pub(crate) const WE_HAVE_AN_X_AND_Y_SCALE: u16 = 0x0040;
+pub(crate) const WE_HAVE_A_TWO_BY_TWO: u16 = 0x0080;
+pub(crate) const USE_MY_METRICS: u16 = 0x0200;
+
+// The maximum nesting of composite glyphs, like in `ttf-parser`.
+const MAX_DEPTH: u8 = 32;
+
+/// A component of a composite glyph in the `glyf` table.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Component {
+    /// The glyph the component refers to, which can be a composite itself.
+    pub glyph_id: GlyphId,
+    /// The transform from the component glyph to the composite.
+    pub transform: Transform,
+    /// The point indices of the composite and the component that are matched to
+    /// position the component, if it's positioned that way.
+    ///
+    /// The transform has no offset in that case, which is also how `Outline::new`
+    /// places such components.
+    pub anchor: Option<(u16, u16)>,
+    /// Whether the composite uses the metrics of this component.
+    pub use_my_metrics: bool,
+}
+
+impl Component {
+    /// Returns the direct components of a glyph, which is empty when the glyph isn't
+    /// a composite or the face has no `glyf` table.
+    ///
+    /// Offsets are the ones of the default instance of a variable font.
+    pub fn list(face: &Face, glyph_id: GlyphId) -> Vec<Component> {
+        glyph_data(face, glyph_id)
+            .and_then(parse_components)
+            .unwrap_or_default()
+    }
+
+    /// Returns the outline of the component, placed like in the composite, or `None`
+    /// when the component glyph has no outline or on error.
+    ///
+    /// Use `Outline::new` with `glyph_id` for the untransformed outline.
+    pub fn outline(&self, face: &Face) -> Option<Outline> {
+        let mut outline = Outline::new(face, self.glyph_id)?;
+        if !self.transform.is_identity() {
+            outline.transform(self.transform);
+        }
+
+        Some(outline)
+    }
+}
+
+/// A component in the component tree of an outline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutlineComponent {
+    /// The component, with the transform relative to its parent.
+    pub component: Component,
+    /// The contours of the outline that come from the component.
+    pub contours: Range<usize>,
+    /// The components of the component glyph, when it's a composite itself.
+    pub children: Vec<OutlineComponent>,
+}
+
+impl Outline {
+    /// Returns a new outline that keeps the component tree of a composite glyph or
+    /// `None` when the glyph has no outline or on error.
+    ///
+    /// The contours are the same as with `new`. Glyphs that aren't composites have no
+    /// components.
+    pub fn new_with_components(face: &Face, glyph_id: GlyphId) -> Option<Self> {
+        let components = Component::list(face, glyph_id);
+        if components.is_empty() {
+            return Self::new(face, glyph_id);
+        }
+
+        let mut outline = Outline::empty(false);
+        outline.components = outline.push_components(face, components, Transform::identity(), 0);
+        for (i, c) in outline.contours.iter_mut().enumerate() {
+            c.source_index = i;
+        }
+
+        if outline.is_empty() {
+            return None;
+        }
+
+        Some(outline)
+    }
+
+    /// Returns the top level components, which is empty unless the outline was built
+    /// with `new_with_components`.
+    ///
+    /// Affine transforms of the whole outline, like `transform` and `translate`, are
+    /// applied to the component transforms as well. Other edits of the contours
+    /// aren't reflected in the components, and edits that remove or reorder
+    /// contours, like `remove_overlaps` and `sort_contours`, drop them.
+    #[inline]
+    pub fn components(&self) -> &[OutlineComponent] {
+        &self.components
+    }
+
+    /// Replaces the contours of every component, at any depth, that uses `glyph_id`
+    /// with `outline`, placed by the transforms of the component tree.
+    ///
+    /// This propagates an edited base glyph to its composites. The replaced
+    /// components lose their children and the contour ranges of the tree are updated.
+    /// Returns `None` when no component uses the glyph.
+    pub fn replace_component(&mut self, glyph_id: GlyphId, outline: &Outline) -> Option<()> {
+        let mut components = std::mem::take(&mut self.components);
+        let mut contours = Vec::with_capacity(self.contours.len());
+        let mut cursor = 0;
+        let replaced = replace_components(
+            &mut components,
+            &self.contours,
+            &mut contours,
+            &mut cursor,
+            Transform::identity(),
+            glyph_id,
+            outline,
+        );
+        self.components = components;
+        if !replaced {
+            return None;
+        }
+
+        contours.extend_from_slice(&self.contours[cursor..]);
+        for (i, c) in contours.iter_mut().enumerate() {
+            c.source_index = i;
+        }
+
+        self.contours = contours;
+        self.invalidate_bounds();
+        Some(())
+    }
+
+    pub(crate) fn transform_components(&mut self, ts: Transform) {
+        for node in &mut self.components {
+            node.component.transform = node.component.transform.then(&ts);
+        }
+    }
+
+    fn push_components(
+        &mut self,
+        face: &Face,
+        components: Vec<Component>,
+        ts: Transform,
+        depth: u8,
+    ) -> Vec<OutlineComponent> {
+        let mut nodes = Vec::with_capacity(components.len());
+        for component in components {
+            let start = self.contours.len();
+            let ts = component.transform.then(&ts);
+            let nested = Component::list(face, component.glyph_id);
+            let children = if nested.is_empty() || depth + 1 >= MAX_DEPTH {
+                if let Some(mut outline) = Outline::new(face, component.glyph_id) {
+                    outline.transform(ts);
+                    self.contours.append(&mut outline.contours);
+                }
+
+                Vec::new()
+            } else {
+                self.push_components(face, nested, ts, depth + 1)
+            };
+
+            nodes.push(OutlineComponent {
+                component,
+                contours: start..self.contours.len(),
+                children,
+            });
+        }
+
+        nodes
+    }
+}
+
+// Copies the contours of the components to `contours`, replacing the ones that use
+// the glyph. Contours outside of the components, up to their ends, are kept.
+fn replace_components(
+    nodes: &mut [OutlineComponent],
+    old: &[Contour],
+    contours: &mut Vec<Contour>,
+    cursor: &mut usize,
+    ts: Transform,
+    glyph_id: GlyphId,
+    outline: &Outline,
+) -> bool {
+    let mut replaced = false;
+    for node in nodes {
+        contours.extend_from_slice(&old[*cursor..node.contours.start]);
+        let start = contours.len();
+        let node_ts = node.component.transform.then(&ts);
+        if node.component.glyph_id == glyph_id {
+            let mut outline = outline.clone();
+            outline.transform(node_ts);
+            contours.append(&mut outline.contours);
+            node.children.clear();
+            replaced = true;
+        } else {
+            *cursor = node.contours.start;
+            replaced |= replace_components(
+                &mut node.children,
+                old,
+                contours,
+                cursor,
+                node_ts,
+                glyph_id,
+                outline,
+            );
+            contours.extend_from_slice(&old[*cursor..node.contours.end]);
+        }
+
+        *cursor = node.contours.end;
+        node.contours = start..contours.len();
+    }
+
+    replaced
+}
+
+// Returns the `glyf` table entry of a glyph.
+pub(crate) fn glyph_data<'a>(face: &Face<'a>, glyph_id: GlyphId) -> Option<&'a [u8]> {
+    let head = face.table_data(Tag::from_bytes(b"head"))?;
+    let loca = face.table_data(Tag::from_bytes(b"loca"))?;
+    let glyf = face.table_data(Tag::from_bytes(b"glyf"))?;
+    let index = glyph_id.0 as usize;
+    let (start, end) = if read_i16(head, 50)? == 0 {
+        let start = read_u16(loca, index * 2)? as usize * 2;
+        let end = read_u16(loca, index * 2 + 2)? as usize * 2;
+        (start, end)
+    } else {
+        let start = read_u32(loca, index * 4)? as usize;
+        let end = read_u32(loca, index * 4 + 4)? as usize;
+        (start, end)
+    };
+
+    if start >= end {
+        return None;
+    }
+
+    glyf.get(start..end)
+}
+
+fn parse_components(data: &[u8]) -> Option<Vec<Component>> {
+    if read_i16(data, 0)? >= 0 {
+        return None;
+    }
+
+    let mut components = Vec::new();
+    // Skip the number of contours and the bbox.
+    let mut offset = 10;
+    loop {
+        let flags = read_u16(data, offset)?;
+        let glyph_id = GlyphId(read_u16(data, offset + 2)?);
+        offset += 4;
+
+        let (arg1, arg2) = if flags & ARG_1_AND_2_ARE_WORDS != 0 {
+            offset += 4;
+            (read_u16(data, offset - 4)?, read_u16(data, offset - 2)?)
+        } else {
+            offset += 2;
+            (
+                u16::from(read_u8(data, offset - 2)?),
+                u16::from(read_u8(data, offset - 1)?),
+            )
+        };
+
+        let mut transform = Transform::identity();
+        let anchor = if flags & ARGS_ARE_XY_VALUES != 0 {
+            if flags & ARG_1_AND_2_ARE_WORDS != 0 {
+                transform.e = f32::from(arg1 as i16);
+                transform.f = f32::from(arg2 as i16);
+            } else {
+                transform.e = f32::from(arg1 as u8 as i8);
+                transform.f = f32::from(arg2 as u8 as i8);
+            }
+
+            None
+        } else {
+            Some((arg1, arg2))
+        };
+
+        if flags & WE_HAVE_A_TWO_BY_TWO != 0 {
+            transform.a = read_f2dot14(data, offset)?;
+            transform.b = read_f2dot14(data, offset + 2)?;
+            transform.c = read_f2dot14(data, offset + 4)?;
+            transform.d = read_f2dot14(data, offset + 6)?;
+            offset += 8;
+        } else if flags & WE_HAVE_AN_X_AND_Y_SCALE != 0 {
+            transform.a = read_f2dot14(data, offset)?;
+            transform.d = read_f2dot14(data, offset + 2)?;
+            offset += 4;
+        } else if flags & WE_HAVE_A_SCALE != 0 {
+            transform.a = read_f2dot14(data, offset)?;
+            transform.d = transform.a;
+            offset += 2;
+        }
+
+        components.push(Component {
+            glyph_id,
+            transform,
+            anchor,
+            use_my_metrics: flags & USE_MY_METRICS != 0,
+        });
+
+        if flags & MORE_COMPONENTS == 0 {
+            break;
+        }
+    }
+
+    Some(components)
+}
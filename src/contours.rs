@@ -0,0 +1,230 @@
+use crate::{Contour, Outline, PathVerb};
+
+/// The direction of a contour.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Winding {
+    /// Clockwise in a y-up coordinate system.
+    Clockwise,
+    /// Counter-clockwise in a y-up coordinate system.
+    CounterClockwise,
+}
+
+/// The direction of outer contours, with holes running the opposite way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WindingConvention {
+    /// Clockwise outer contours, as in `glyf` tables.
+    TrueType,
+    /// Counter-clockwise outer contours, as in CFF tables.
+    PostScript,
+}
+
+/// The order of contours within an outline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ContourOrdering {
+    /// The order of the source glyph, see `Contour::source_index`.
+    Source,
+    /// Outer contours before the holes within them, by nesting depth.
+    OuterFirst,
+    /// Largest area first.
+    ByArea,
+}
+
+/// A contour in the nesting tree of an outline.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ContourNode {
+    /// The index of the contour in the outline.
+    pub index: usize,
+    /// Whether the contour is a hole, i.e. nested within an odd number of contours.
+    pub is_hole: bool,
+    /// The contours directly inside this one.
+    pub children: Vec<ContourNode>,
+}
+
+impl Contour {
+    /// Returns the index of the contour in the source glyph.
+    ///
+    /// The index is kept when contours are reordered or modified. Contours added with
+    /// `move_to` are indexed by their position at that time, and outlines that are
+    /// rebuilt, e.g. by `remove_overlaps`, start over.
+    #[inline]
+    pub fn source_index(&self) -> usize {
+        self.source_index
+    }
+
+    /// Returns the direction of the contour or `None` when it has no area.
+    pub fn winding(&self) -> Option<Winding> {
+        let area = self.signed_area();
+        if area > 0.0 {
+            Some(Winding::CounterClockwise)
+        } else if area < 0.0 {
+            Some(Winding::Clockwise)
+        } else {
+            None
+        }
+    }
+}
+
+impl Outline {
+    /// Reverses contours so they follow the winding convention.
+    ///
+    /// Contours nested within an even number of other contours are outer contours and
+    /// the rest are holes. Subsequent calls to `embolden` follow the new convention.
+    pub fn normalize_windings(&mut self, convention: WindingConvention) {
+        let outer = match convention {
+            WindingConvention::TrueType => Winding::Clockwise,
+            WindingConvention::PostScript => Winding::CounterClockwise,
+        };
+
+        let depths = self.contour_depths();
+        for (c, depth) in self.contours.iter_mut().zip(depths) {
+            let winding = match c.winding() {
+                Some(winding) => winding,
+                None => continue,
+            };
+
+            if (winding == outer) != (depth % 2 == 0) {
+                c.reverse();
+            }
+        }
+
+        self.cff = convention == WindingConvention::PostScript;
+    }
+
+    /// Reverses the direction of a single contour.
+    ///
+    /// Returns `None` when the contour index is out of bounds.
+    pub fn reverse_contour(&mut self, index: usize) -> Option<()> {
+        self.contours.get_mut(index)?.reverse();
+        Some(())
+    }
+
+    /// Reverses the direction of all contours.
+    ///
+    /// This switches between the TrueType and PostScript winding conventions, which
+    /// subsequent calls to `embolden` follow.
+    pub fn reverse_all(&mut self) {
+        for c in &mut self.contours {
+            c.reverse();
+        }
+
+        self.cff = !self.cff;
+    }
+
+    /// Returns the indices of the contours that don't end with a close command.
+    pub fn open_contours(&self) -> Vec<usize> {
+        self.contours
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| !c.is_closed())
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Closes all open contours.
+    ///
+    /// No points are added, i.e. the line from the last point back to the first one
+    /// is implied like for any closed contour.
+    pub fn close_all(&mut self) {
+        for c in &mut self.contours {
+            if !c.is_closed() {
+                c.verbs.push(PathVerb::Close);
+            }
+        }
+    }
+
+    /// Returns the nesting tree of the contours.
+    ///
+    /// The returned nodes are the contours that aren't inside any other contour. A
+    /// contour is a child of the smallest contour containing it, assuming contours
+    /// don't intersect.
+    pub fn contour_tree(&self) -> Vec<ContourNode> {
+        fn node(index: usize, is_hole: bool, parents: &[Option<usize>]) -> ContourNode {
+            let children = (0..parents.len())
+                .filter(|&i| parents[i] == Some(index))
+                .map(|i| node(i, !is_hole, parents))
+                .collect();
+            ContourNode {
+                index,
+                is_hole,
+                children,
+            }
+        }
+
+        let parents = self.contour_parents();
+        (0..parents.len())
+            .filter(|&i| parents[i].is_none())
+            .map(|i| node(i, false, &parents))
+            .collect()
+    }
+
+    /// Reorders the contours.
+    ///
+    /// The sort is stable, so contours that compare equal keep their relative order.
+    pub fn sort_contours(&mut self, ordering: ContourOrdering) {
+        self.components.clear();
+        match ordering {
+            ContourOrdering::Source => self.contours.sort_by_key(|c| c.source_index),
+            ContourOrdering::OuterFirst => {
+                let depths = self.contour_depths();
+                let mut contours: Vec<_> = self.contours.drain(..).zip(depths).collect();
+                contours.sort_by_key(|(_, depth)| *depth);
+                self.contours = contours.into_iter().map(|(c, _)| c).collect();
+            }
+            ContourOrdering::ByArea => {
+                let mut contours: Vec<_> = self
+                    .contours
+                    .drain(..)
+                    .map(|c| (c.signed_area().abs(), c))
+                    .collect();
+                contours.sort_by(|a, b| b.0.total_cmp(&a.0));
+                self.contours = contours.into_iter().map(|(_, c)| c).collect();
+            }
+        }
+    }
+
+    // Returns how many contours each contour is nested within.
+    fn contour_depths(&self) -> Vec<usize> {
+        let parents = self.contour_parents();
+        (0..parents.len())
+            .map(|i| {
+                let mut depth = 0;
+                let mut parent = parents[i];
+                while let Some(p) = parent {
+                    depth += 1;
+                    parent = parents[p];
+                }
+
+                depth
+            })
+            .collect()
+    }
+
+    // Returns the index of the smallest contour containing each contour, assuming
+    // contours don't intersect.
+    fn contour_parents(&self) -> Vec<Option<usize>> {
+        let areas: Vec<f32> = self
+            .contours
+            .iter()
+            .map(|c| c.signed_area().abs())
+            .collect();
+        self.contours
+            .iter()
+            .enumerate()
+            .map(|(i, c)| {
+                let p = *c.points.first()?;
+                self.contours
+                    .iter()
+                    .enumerate()
+                    .filter(|&(j, other)| {
+                        j != i && areas[j] > areas[i] && other.winding_number(p) != 0
+                    })
+                    .min_by(|a, b| areas[a.0].total_cmp(&areas[b.0]))
+                    .map(|(j, _)| j)
+            })
+            .collect()
+    }
+}
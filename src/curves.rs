@@ -0,0 +1,95 @@
+use crate::geom::cubic_to_quads;
+use crate::{Outline, PathVerb, Point};
+
+impl Outline {
+    /// Returns the outline with all curves flattened into lines.
+    ///
+    /// Curves are subdivided until the lines are within `tolerance` of the curve.
+    pub fn flatten(&self, tolerance: f32) -> Outline {
+        let mut outline = Outline::empty(self.cff);
+        for c in &self.contours {
+            let start = match c.points.first() {
+                Some(p) => *p,
+                None => continue,
+            };
+
+            outline.move_to(start.x, start.y);
+            for segment in c.segments() {
+                segment.flatten(tolerance, |p| outline.line_to(p.x, p.y));
+            }
+
+            if c.is_closed() {
+                outline.close();
+            }
+        }
+
+        outline
+    }
+
+    /// Converts all quadratic curves into cubic ones.
+    ///
+    /// The conversion is exact, i.e. the shape of the outline doesn't change.
+    pub fn to_cubics(&mut self) {
+        // Only the control points move, so the ink box stays valid.
+        self.control_box.set(None);
+        for c in &mut self.contours {
+            if !c.verbs.iter().any(|v| matches!(v, PathVerb::QuadTo)) {
+                continue;
+            }
+
+            let mut points: Vec<Point> = Vec::with_capacity(c.points.len() + c.verbs.len());
+            let mut src = c.points.iter().copied();
+            for v in &mut c.verbs {
+                if let PathVerb::QuadTo = v {
+                    let p0 = *points.last().unwrap();
+                    let p1 = src.next().unwrap();
+                    let p2 = src.next().unwrap();
+                    points.push(p0.lerp(p1, 2.0 / 3.0));
+                    points.push(p2.lerp(p1, 2.0 / 3.0));
+                    points.push(p2);
+                    *v = PathVerb::CurveTo;
+                } else {
+                    points.extend(src.by_ref().take(v.num_points()));
+                }
+            }
+
+            c.points = points;
+        }
+    }
+
+    /// Approximates all cubic curves with quadratic ones.
+    ///
+    /// Each cubic curve is split until every quadratic curve deviates at most
+    /// `max_error` from it.
+    pub fn to_quadratics(&mut self, max_error: f32) {
+        self.invalidate_bounds();
+        for c in &mut self.contours {
+            if !c.verbs.iter().any(|v| matches!(v, PathVerb::CurveTo)) {
+                continue;
+            }
+
+            let mut verbs = Vec::with_capacity(c.verbs.len());
+            let mut points: Vec<Point> = Vec::with_capacity(c.points.len());
+            let mut src = c.points.iter().copied();
+            for &v in &c.verbs {
+                if let PathVerb::CurveTo = v {
+                    let p0 = *points.last().unwrap();
+                    let p1 = src.next().unwrap();
+                    let p2 = src.next().unwrap();
+                    let p3 = src.next().unwrap();
+                    for (q, p) in cubic_to_quads(p0, p1, p2, p3, max_error) {
+                        verbs.push(PathVerb::QuadTo);
+                        points.push(q);
+                        points.push(p);
+                    }
+                } else {
+                    verbs.push(v);
+                    points.extend(src.by_ref().take(v.num_points()));
+                }
+            }
+
+            c.verbs = verbs;
+            c.points = points;
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use crate::{Outline, Point};
+
+/// A line drawn along a run of text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Decoration {
+    /// An underline, positioned by the `post` table.
+    Underline,
+    /// A strikeout, positioned by the `OS/2` table.
+    Strikeout,
+}
+
+/// How highlight boxes are built for a run of glyphs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HighlightMode {
+    /// A box for every glyph.
+    PerGlyph,
+    /// A single box for the whole run.
+    Merged,
+}
+
+impl Outline {
+    /// Returns the rectangle of a decoration along a run of text or `None` when the
+    /// face has no metrics for it.
+    ///
+    /// The rectangle starts at `x = 0` and is `width` font units long. Pass the same
+    /// `x_skew` as to `oblique` to match slanted glyphs, or zero for upright ones.
+    pub fn new_decoration(
+        face: &ttf_parser::Face,
+        decoration: Decoration,
+        width: f32,
+        x_skew: f32,
+    ) -> Option<Self> {
+        let metrics = match decoration {
+            Decoration::Underline => face.underline_metrics()?,
+            Decoration::Strikeout => face.strikeout_metrics()?,
+        };
+
+        // The position is the top of the line in both tables.
+        let top = metrics.position as f32;
+        let bottom = top - metrics.thickness as f32;
+        let mut outline = Outline::default();
+        outline.push_rect(0.0, bottom, width, top);
+        if x_skew != 0.0 {
+            outline.oblique(x_skew);
+        }
+
+        Some(outline)
+    }
+
+    /// Returns highlight boxes, e.g. for a text selection, behind a run of glyphs.
+    ///
+    /// The glyphs are outlines paired with their advance widths, laid out from
+    /// `x = 0`. Each box spans the advance of its glyphs horizontally and the ink of
+    /// the whole run vertically, so all boxes have the same height. Corners are
+    /// rounded with `corner_radius` unless it's zero.
+    pub fn highlight_boxes(
+        glyphs: &[(&Outline, f32)],
+        mode: HighlightMode,
+        corner_radius: f32,
+    ) -> Vec<Outline> {
+        let mut bottom = f32::INFINITY;
+        let mut top = f32::NEG_INFINITY;
+        for (outline, _) in glyphs.iter().filter(|(outline, _)| !outline.is_empty()) {
+            let bbox = outline.ink_box();
+            bottom = bottom.min(bbox.y_min);
+            top = top.max(bbox.y_max);
+        }
+
+        if bottom >= top {
+            return Vec::new();
+        }
+
+        let mut spans = Vec::new();
+        let mut x = 0.0;
+        for &(_, advance) in glyphs {
+            spans.push((x, x + advance));
+            x += advance;
+        }
+
+        if mode == HighlightMode::Merged {
+            spans = vec![(0.0, x)];
+        }
+
+        spans
+            .into_iter()
+            .filter(|(x0, x1)| x0 != x1)
+            .map(|(x0, x1)| {
+                let mut outline = Outline::default();
+                outline.push_rect(x0.min(x1), bottom, x0.max(x1), top);
+                if corner_radius > 0.0 {
+                    outline.round_corners(corner_radius);
+                }
+
+                outline
+            })
+            .collect()
+    }
+
+    // Adds a rectangle running clockwise, like outer contours in `glyf` tables.
+    fn push_rect(&mut self, x0: f32, y0: f32, x1: f32, y1: f32) {
+        self.push_polygon(vec![
+            Point::new(x0, y0),
+            Point::new(x0, y1),
+            Point::new(x1, y1),
+            Point::new(x1, y0),
+        ]);
+    }
+}
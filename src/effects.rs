@@ -0,0 +1,456 @@
+use crate::geom::{Segment, MAX_BISECTIONS};
+use crate::{Contour, Outline, Point};
+
+// Directions that differ by less than this many radians don't form a corner.
+const SMOOTH_ANGLE: f32 = 0.02;
+
+impl Outline {
+    /// Replaces sharp corners with round fillets.
+    ///
+    /// Each corner gets a circular fillet of `radius`, approximated with a cubic
+    /// curve. The radius is reduced where the adjacent segments are too short, so a
+    /// fillet never takes up more than half of a segment.
+    pub fn round_corners(&mut self, radius: f32) {
+        if radius <= 0.0 {
+            return;
+        }
+
+        self.invalidate_bounds();
+        for c in &mut self.contours {
+            let closed = c.is_closed();
+            let closing_line = if closed { c.closing_line() } else { None };
+            let segments: Vec<Segment> = c
+                .segments()
+                .chain(closing_line)
+                .filter(|segment| segment.start() != segment.end())
+                .collect();
+            let n = segments.len();
+            if n < 2 {
+                continue;
+            }
+
+            // How far each segment is cut back at its start and end.
+            let mut trims = vec![(0.0f32, 0.0f32); n];
+            let junctions = if closed { 0..n } else { 1..n };
+            for i in junctions {
+                let prev = (i + n - 1) % n;
+                let (d0, d1) = match (segments[prev].end_tangent(), segments[i].start_tangent()) {
+                    (Some(d0), Some(d1)) => (d0, d1),
+                    _ => continue,
+                };
+
+                let angle = (d0.x * d1.y - d0.y * d1.x)
+                    .atan2(d0.x * d1.x + d0.y * d1.y)
+                    .abs();
+                if angle < SMOOTH_ANGLE {
+                    continue;
+                }
+
+                let chord = |s: &Segment| s.start().distance(s.end()) * 0.5;
+                let trim = (radius * (angle * 0.5).tan())
+                    .min(chord(&segments[prev]))
+                    .min(chord(&segments[i]));
+                trims[prev].1 = trim;
+                trims[i].0 = trim;
+            }
+
+            let trimmed: Vec<Segment> = segments
+                .iter()
+                .zip(&trims)
+                .map(|(segment, &(start, end))| trim(segment, start, end))
+                .collect();
+            let mut out = Vec::with_capacity(n * 2);
+            for i in 0..n {
+                out.push(trimmed[i]);
+                let next = (i + 1) % n;
+                if (closed || next != 0) && trims[i].1 > 0.0 {
+                    out.push(fillet(&trimmed[i], &trimmed[next]));
+                }
+            }
+
+            let start = out[0].start();
+            c.set_segments(start, out, closed);
+        }
+    }
+
+    /// Returns the outline split into dashes.
+    ///
+    /// The pattern alternates between the lengths of dashes and gaps, measured along
+    /// the contours, and is repeated when it has an odd number of entries. `offset`
+    /// is how far into the pattern each contour starts. Dashes are open contours with
+    /// the curves of the original ones, and a dash running across the start of a
+    /// closed contour is joined into one. The outline is returned as is when the
+    /// pattern is empty, negative or zero in total, and contours are kept whole when
+    /// the shortest dash or gap is too short to be measured along them.
+    pub fn dash(&self, pattern: &[f32], offset: f32) -> Outline {
+        let total: f32 = pattern.iter().sum();
+        if total <= 0.0 || pattern.iter().any(|&len| len < 0.0 || !len.is_finite()) {
+            return self.clone();
+        }
+
+        let pattern: Vec<f32> = if pattern.len() % 2 == 1 {
+            pattern.iter().chain(pattern).copied().collect()
+        } else {
+            pattern.to_vec()
+        };
+        let total: f32 = pattern.iter().sum();
+        let shortest = pattern
+            .iter()
+            .copied()
+            .filter(|&len| len > 0.0)
+            .fold(f32::INFINITY, f32::min);
+        // Entries below the precision of a position don't move it, so the pattern
+        // would never advance.
+        if shortest <= total * f32::EPSILON {
+            return self.clone();
+        }
+
+        let tolerance = self.flatten_tolerance(shortest);
+
+        // The pattern entry and how much of it is left at the start of each contour.
+        let mut start = (0, pattern[0]);
+        let mut pos = offset.rem_euclid(total);
+        while pos > 0.0 && pos >= start.1 {
+            pos -= start.1;
+            start.0 = (start.0 + 1) % pattern.len();
+            start.1 = pattern[start.0];
+        }
+        start.1 -= pos;
+
+        let mut outline = Outline::empty(self.cff);
+        for c in &self.contours {
+            let closed = c.is_closed();
+            let closing_line = if closed { c.closing_line() } else { None };
+            let segments: Vec<(Segment, f32)> = c
+                .segments()
+                .chain(closing_line)
+                .map(|segment| (segment, segment.arc_length(tolerance)))
+                .collect();
+            let len: f32 = segments.iter().map(|&(_, len)| len).sum();
+            if shortest <= len * f32::EPSILON {
+                outline.contours.push(c.clone());
+                continue;
+            }
+
+            let (mut index, mut remaining) = start;
+            let mut dashes: Vec<Vec<Segment>> = Vec::new();
+            let mut current: Vec<Segment> = Vec::new();
+            let mut toggled = false;
+            for (segment, len) in segments {
+                let mut t = 0.0;
+                let mut pos = 0.0;
+                while len - pos > remaining {
+                    pos += remaining;
+                    let t1 = parameter_at(&segment, pos, len, tolerance);
+                    if index % 2 == 0 {
+                        current.push(segment.subsegment(t, t1));
+                        dashes.push(std::mem::take(&mut current));
+                    }
+
+                    t = t1;
+                    index = (index + 1) % pattern.len();
+                    remaining = pattern[index];
+                    toggled = true;
+                }
+
+                if index % 2 == 0 {
+                    current.push(segment.subsegment(t, 1.0));
+                }
+
+                remaining -= len - pos;
+            }
+
+            if !toggled && closed && !current.is_empty() {
+                // The whole contour is a single dash.
+                outline.contours.push(c.clone());
+                continue;
+            }
+
+            if !current.is_empty() {
+                if closed && start.0 % 2 == 0 && !dashes.is_empty() {
+                    current.append(&mut dashes[0]);
+                    dashes[0] = current;
+                } else {
+                    dashes.push(current);
+                }
+            }
+
+            for segments in dashes {
+                let mut dash = Contour {
+                    source_index: c.source_index,
+                    ..Contour::default()
+                };
+                dash.set_segments(segments[0].start(), segments, false);
+                outline.contours.push(dash);
+            }
+        }
+
+        outline
+    }
+
+    /// Returns the ring between the outline and its offset by `thickness`.
+    ///
+    /// A positive thickness adds the ring outside of the glyph and a negative one
+    /// inside of it, i.e. the result is the offset outline with the original one
+    /// cut out, or the other way around. See `offset` for how curves are handled.
+    pub fn outline_effect(&self, thickness: f32) -> Outline {
+        let mut outline = Outline::empty(self.cff);
+        if thickness == 0.0 {
+            return outline;
+        }
+
+        let tolerance = self.flatten_tolerance(thickness.abs());
+        let mut shape = self.clone();
+        shape.remove_overlaps(tolerance);
+
+        // Both outlines wind the same way after removing overlaps, so reversing the
+        // original one leaves a winding number of zero where they overlap.
+        outline.contours = self.offset(thickness).contours;
+        for mut c in shape.contours {
+            c.reverse();
+            outline.contours.push(c);
+        }
+
+        outline.remove_overlaps_with(tolerance, |winding| winding != 0);
+        outline
+    }
+
+    /// Returns copies of the outline translated by each of the offsets.
+    ///
+    /// The copies are in the same order as the offsets, so list the one at the back
+    /// first and fill them in order, e.g. for drop shadows or block shadows.
+    pub fn layered(&self, offsets: &[(f32, f32)]) -> Vec<Outline> {
+        offsets
+            .iter()
+            .map(|&(dx, dy)| {
+                let mut outline = self.clone();
+                outline.translate(dx, dy);
+                outline
+            })
+            .collect()
+    }
+
+    /// Gives the outline a hand-drawn look.
+    ///
+    /// The contours are resampled about every `segment_length` units along their
+    /// length, the points are moved randomly by up to `amplitude` units along both
+    /// axes, and smooth cubic curves are fitted through them. The same `seed` always
+    /// gives the same result.
+    pub fn roughen(&mut self, amplitude: f32, segment_length: f32, seed: u64) {
+        if segment_length <= 0.0 {
+            return;
+        }
+
+        self.invalidate_bounds();
+        let tolerance = self.flatten_tolerance(segment_length);
+        let mut rng = SplitMix64(seed);
+        for c in &mut self.contours {
+            let closed = c.is_closed();
+            let mut points = c.flatten_points(tolerance);
+            points.dedup();
+            if closed {
+                points.extend(points.first().copied());
+            }
+
+            if points.len() < 2 {
+                continue;
+            }
+
+            let mut samples = resample(&points, segment_length, closed);
+            for p in &mut samples {
+                p.x += rng.next_f32() * amplitude;
+                p.y += rng.next_f32() * amplitude;
+            }
+
+            // Catmull-Rom splines through the samples as cubic curves.
+            let n = samples.len();
+            let at = |i: isize| {
+                if closed {
+                    samples[i.rem_euclid(n as isize) as usize]
+                } else {
+                    samples[i.clamp(0, n as isize - 1) as usize]
+                }
+            };
+            let count = if closed { n } else { n - 1 };
+            let segments = (0..count as isize).map(|i| {
+                let (p0, p1, p2, p3) = (at(i - 1), at(i), at(i + 1), at(i + 2));
+                Segment::Cubic(
+                    p1,
+                    p1 + (p2 - p0) * (1.0 / 6.0),
+                    p2 - (p3 - p1) * (1.0 / 6.0),
+                    p2,
+                )
+            });
+            c.set_segments(samples[0], segments, closed);
+        }
+    }
+}
+
+// Returns points at equal distances along a polyline, about `spacing` units apart. The
+// last point is left out for closed polylines, which end at their start.
+fn resample(points: &[Point], spacing: f32, closed: bool) -> Vec<Point> {
+    let lengths: Vec<f32> = points.windows(2).map(|w| w[0].distance(w[1])).collect();
+    let total: f32 = lengths.iter().sum();
+    let min_steps = if closed { 3.0 } else { 1.0 };
+    let steps = (total / spacing).round().max(min_steps) as usize;
+    let step = total / steps as f32;
+
+    let mut samples = Vec::with_capacity(steps + 1);
+    let mut i = 0;
+    let mut start = 0.0;
+    for k in 0..steps {
+        let pos = k as f32 * step;
+        while i + 1 < lengths.len() && start + lengths[i] < pos {
+            start += lengths[i];
+            i += 1;
+        }
+
+        let t = if lengths[i] > 0.0 {
+            ((pos - start) / lengths[i]).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        samples.push(points[i].lerp(points[i + 1], t));
+    }
+
+    if !closed {
+        samples.extend(points.last().copied());
+    }
+
+    samples
+}
+
+// A small deterministic random number generator.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    // Returns a number in `[-1, 1)`.
+    fn next_f32(&mut self) -> f32 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^= z >> 31;
+        (z >> 40) as f32 / (1u32 << 23) as f32 - 1.0
+    }
+}
+
+// Returns the parameter at arc length `pos` along a segment of length `len`.
+fn parameter_at(segment: &Segment, pos: f32, len: f32, tolerance: f32) -> f32 {
+    if pos <= 0.0 {
+        return 0.0;
+    } else if pos >= len {
+        return 1.0;
+    }
+
+    if let Segment::Line(..) = segment {
+        return pos / len;
+    }
+
+    let (mut lo, mut hi) = (0.0, 1.0);
+    for _ in 0..MAX_BISECTIONS {
+        let mid = (lo + hi) * 0.5;
+        if segment.subsegment(0.0, mid).arc_length(tolerance) < pos {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+
+    (lo + hi) * 0.5
+}
+
+// Cuts a segment back by the given distances from its start and end point.
+fn trim(segment: &Segment, start: f32, end: f32) -> Segment {
+    let t0 = if start > 0.0 {
+        let p = segment.start();
+        find_distance(segment, p, start, true)
+    } else {
+        0.0
+    };
+    let t1 = if end > 0.0 {
+        let p = segment.end();
+        find_distance(segment, p, end, false)
+    } else {
+        1.0
+    };
+
+    segment.subsegment(t0, t1)
+}
+
+// Returns the parameter at `distance` from the end point `p`, searching from the start
+// when `from_start` is set and from the end otherwise.
+fn find_distance(segment: &Segment, p: Point, distance: f32, from_start: bool) -> f32 {
+    let (mut near, mut far) = if from_start { (0.0, 1.0) } else { (1.0, 0.0) };
+    for _ in 0..MAX_BISECTIONS {
+        let mid = (near + far) * 0.5;
+        if segment.eval(mid).distance(p) < distance {
+            near = mid;
+        } else {
+            far = mid;
+        }
+    }
+
+    (near + far) * 0.5
+}
+
+// Returns a cubic curve approximating a circular arc from the end of `a` to the start
+// of `b` that is tangent to both.
+fn fillet(a: &Segment, b: &Segment) -> Segment {
+    let p0 = a.end();
+    let p3 = b.start();
+    let (d0, d1) = match (a.end_tangent(), b.start_tangent()) {
+        (Some(d0), Some(d1)) => (d0 * (1.0 / d0.length()), d1 * (1.0 / d1.length())),
+        _ => return Segment::Line(p0, p3),
+    };
+
+    let sweep = (d0.x * d1.y - d0.y * d1.x)
+        .atan2(d0.x * d1.x + d0.y * d1.y)
+        .abs();
+    let chord = p0.distance(p3);
+    let half_sin = (sweep * 0.5).sin();
+    if half_sin <= f32::EPSILON {
+        return Segment::Line(p0, p3);
+    }
+
+    // The radius of the arc through both points and the handle length of its cubic.
+    let radius = chord / (2.0 * half_sin);
+    let handle = 4.0 / 3.0 * (sweep * 0.25).tan() * radius;
+    Segment::Cubic(p0, p0 + d0 * handle, p3 - d1 * handle, p3)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Outline;
+
+    fn square(size: f32) -> Outline {
+        let mut outline = Outline::default();
+        outline.move_to(0.0, 0.0);
+        outline.line_to(size, 0.0);
+        outline.line_to(size, size);
+        outline.line_to(0.0, size);
+        outline.close();
+        outline
+    }
+
+    #[test]
+    fn dash() {
+        let dashed = square(100.0).dash(&[30.0, 20.0], 0.0);
+        assert_eq!(dashed.num_contours(), 8);
+        assert!(dashed.contours().iter().all(|c| !c.is_closed()));
+    }
+
+    #[test]
+    fn dash_tiny_pattern() {
+        for &(pattern, offset) in &[
+            (&[1e-6, 1e-6][..], 0.0),
+            (&[1e-6, 1e-6][..], 1.5e-6),
+            (&[1e-6, 100.0][..], 50.0),
+        ] {
+            let dashed = square(100.0).dash(pattern, offset);
+            assert_eq!(dashed.num_contours(), 1);
+            assert_eq!(dashed.num_points(), 4);
+            assert!(dashed.contours()[0].is_closed());
+        }
+    }
+}
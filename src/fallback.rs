@@ -0,0 +1,135 @@
+use ttf_parser::{Face, GlyphId};
+
+use crate::layout::{Context, Run};
+use crate::{Direction, Glyph, LayoutOptions, Outline};
+
+/// Faces that are tried in order for every character, so text can fall back to
+/// other faces for characters, like emoji, that the first face lacks.
+#[derive(Debug, Clone)]
+pub struct FaceChain<'a> {
+    faces: Vec<Face<'a>>,
+}
+
+impl<'a> FaceChain<'a> {
+    /// Returns a new chain of faces, in the order they're tried.
+    #[inline]
+    pub fn new(faces: Vec<Face<'a>>) -> Self {
+        FaceChain { faces }
+    }
+
+    /// Returns the faces.
+    #[inline]
+    pub fn faces(&self) -> &[Face<'a>] {
+        &self.faces
+    }
+
+    /// Returns the index of the first face that has a glyph for the character along
+    /// with the glyph id, or `None` when no face has one.
+    pub fn glyph_index(&self, c: char) -> Option<(usize, GlyphId)> {
+        self.faces
+            .iter()
+            .enumerate()
+            .find_map(|(i, face)| Some((i, face.glyph_index(c)?)))
+    }
+
+    /// Returns the index of the first face that has a glyph for the character along
+    /// with its outline, in the units of that face, or `None` when no face has a
+    /// glyph or it has no outline.
+    pub fn outline(&self, c: char) -> Option<(usize, Outline)> {
+        let (index, glyph_id) = self.glyph_index(c)?;
+        Some((index, Outline::new(&self.faces[index], glyph_id)?))
+    }
+
+    /// Returns the glyphs of runs of text in visual order, like the
+    /// `outlines_for_runs` function, where each character comes from the first face
+    /// that has it.
+    ///
+    /// Characters that no face has stay in the run of characters around them.
+    /// Glyphs from faces with other units per em than the first face are scaled to
+    /// its units, outlines included, and `Glyph::face_index` tells the faces apart.
+    /// Kerning and mark attachment only apply within a face.
+    pub fn outlines_for_runs(
+        &self,
+        runs: &[(&str, Direction)],
+        options: LayoutOptions,
+    ) -> Vec<Glyph> {
+        let units_per_em = match self.faces.first() {
+            Some(face) => f32::from(face.units_per_em().unwrap_or(1000)),
+            None => return Vec::new(),
+        };
+
+        let contexts: Vec<Context> = self
+            .faces
+            .iter()
+            .map(|face| Context::new(face, options.clone()))
+            .collect();
+
+        let mut glyphs = Vec::new();
+        let mut x = 0.0;
+        for &(text, direction) in runs {
+            let mut face_runs = self.face_runs(text);
+            if direction == Direction::RightToLeft {
+                face_runs.reverse();
+            }
+
+            for (index, text) in face_runs {
+                let face = &self.faces[index];
+                let scale = units_per_em / f32::from(face.units_per_em().unwrap_or(1000));
+                let mut run_glyphs = Vec::new();
+                let mut run_x = 0.0;
+                let run = Run {
+                    text,
+                    direction,
+                    y: 0.0,
+                };
+                contexts[index].push_run(run, &mut run_x, &mut run_glyphs);
+                for mut glyph in run_glyphs {
+                    if scale != 1.0 {
+                        if let Some(outline) = &mut glyph.outline {
+                            outline.scale(scale, scale);
+                        }
+
+                        glyph.advance *= scale;
+                    }
+
+                    glyph.offset = (x + glyph.offset.0 * scale, glyph.offset.1 * scale);
+                    glyph.face_index = index;
+                    glyphs.push(glyph);
+                }
+
+                x += run_x * scale;
+            }
+        }
+
+        glyphs
+    }
+
+    // Splits text into runs of characters from the same face, in logical order.
+    fn face_runs<'t>(&self, text: &'t str) -> Vec<(usize, &'t str)> {
+        let mut runs = Vec::new();
+        let mut start = 0;
+        let mut current = None;
+        for (i, c) in text.char_indices() {
+            let index = match self.glyph_index(c) {
+                Some((index, _)) => index,
+                None => continue,
+            };
+
+            match current {
+                Some(current) if current != index => {
+                    runs.push((current, &text[start..i]));
+                    start = i;
+                }
+                _ => {}
+            }
+
+            current = Some(index);
+        }
+
+        if start < text.len() {
+            runs.push((current.unwrap_or(0), &text[start..]));
+        }
+
+        runs
+    }
+}
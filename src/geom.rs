@@ -0,0 +1,536 @@
+use std::ops::{Add, Mul, Sub};
+
+use crate::{Contour, PathVerb, Point};
+
+impl Point {
+    #[inline]
+    pub(crate) fn lerp(self, other: Point, t: f32) -> Point {
+        self + (other - self) * t
+    }
+
+    #[inline]
+    pub(crate) fn length(self) -> f32 {
+        (self.x * self.x + self.y * self.y).sqrt()
+    }
+
+    #[inline]
+    pub(crate) fn distance(self, other: Point) -> f32 {
+        (other - self).length()
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    #[inline]
+    fn add(self, other: Point) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    #[inline]
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Mul<f32> for Point {
+    type Output = Point;
+
+    #[inline]
+    fn mul(self, s: f32) -> Point {
+        Point::new(self.x * s, self.y * s)
+    }
+}
+
+// Limits the number of lines a single curve is flattened into.
+const MAX_FLATTEN_STEPS: usize = 1024;
+
+// Enough to find a parameter to within `f32` precision.
+pub(crate) const MAX_BISECTIONS: usize = 32;
+
+/// A contour segment with absolute end points.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Segment {
+    Line(Point, Point),
+    Quad(Point, Point, Point),
+    Cubic(Point, Point, Point, Point),
+}
+
+impl Segment {
+    #[inline]
+    pub(crate) fn start(&self) -> Point {
+        match *self {
+            Segment::Line(p0, _) | Segment::Quad(p0, _, _) | Segment::Cubic(p0, _, _, _) => p0,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn end(&self) -> Point {
+        match *self {
+            Segment::Line(_, p) | Segment::Quad(_, _, p) | Segment::Cubic(_, _, _, p) => p,
+        }
+    }
+}
+
+impl Segment {
+    /// Returns the point at `t`.
+    pub(crate) fn eval(&self, t: f32) -> Point {
+        let mt = 1.0 - t;
+        match *self {
+            Segment::Line(p0, p1) => p0.lerp(p1, t),
+            Segment::Quad(p0, p1, p2) => p0 * (mt * mt) + p1 * (2.0 * mt * t) + p2 * (t * t),
+            Segment::Cubic(p0, p1, p2, p3) => {
+                p0 * (mt * mt * mt)
+                    + p1 * (3.0 * mt * mt * t)
+                    + p2 * (3.0 * mt * t * t)
+                    + p3 * (t * t * t)
+            }
+        }
+    }
+
+    /// Returns the derivative at `t`.
+    pub(crate) fn derivative(&self, t: f32) -> Point {
+        let mt = 1.0 - t;
+        match *self {
+            Segment::Line(p0, p1) => p1 - p0,
+            Segment::Quad(p0, p1, p2) => ((p1 - p0) * mt + (p2 - p1) * t) * 2.0,
+            Segment::Cubic(p0, p1, p2, p3) => {
+                ((p1 - p0) * (mt * mt) + (p2 - p1) * (2.0 * mt * t) + (p3 - p2) * (t * t)) * 3.0
+            }
+        }
+    }
+
+    /// Returns the second derivative at `t`.
+    pub(crate) fn second_derivative(&self, t: f32) -> Point {
+        match *self {
+            Segment::Line(..) => Point::default(),
+            Segment::Quad(p0, p1, p2) => (p0 - p1 * 2.0 + p2) * 2.0,
+            Segment::Cubic(p0, p1, p2, p3) => {
+                ((p2 - p1 * 2.0 + p0) * (1.0 - t) + (p3 - p2 * 2.0 + p1) * t) * 6.0
+            }
+        }
+    }
+
+    /// Returns the signed area between the segment and the origin, as used by the
+    /// shoelace formula generalized to curves.
+    pub(crate) fn signed_area(&self) -> f32 {
+        match *self {
+            Segment::Line(p0, p1) => (p0.x * p1.y - p1.x * p0.y) * 0.5,
+            Segment::Quad(p0, p1, p2) => {
+                (p0.x * (2.0 * p1.y + p2.y) + 2.0 * p1.x * (p2.y - p0.y)
+                    - p2.x * (p0.y + 2.0 * p1.y))
+                    / 6.0
+            }
+            Segment::Cubic(p0, p1, p2, p3) => {
+                (p0.x * (6.0 * p1.y + 3.0 * p2.y + p3.y)
+                    + 3.0
+                        * (p1.x * (-2.0 * p0.y + p2.y + p3.y) - p2.x * (p0.y + p1.y - 2.0 * p3.y))
+                    - p3.x * (p0.y + 3.0 * p1.y + 6.0 * p2.y))
+                    / 20.0
+            }
+        }
+    }
+
+    /// Calls `f` with the points of a polyline approximating the segment within
+    /// `tolerance`, excluding the start point.
+    pub(crate) fn flatten<F: FnMut(Point)>(&self, tolerance: f32, mut f: F) {
+        // Wang's formula for the number of uniform steps.
+        let tolerance = tolerance.max(1e-4);
+        let n = match *self {
+            Segment::Line(_, p1) => {
+                f(p1);
+                return;
+            }
+            Segment::Quad(p0, p1, p2) => {
+                let dd = (p0 - p1 * 2.0 + p2).length();
+                (0.25 * dd / tolerance).sqrt()
+            }
+            Segment::Cubic(p0, p1, p2, p3) => {
+                let dd = (p0 - p1 * 2.0 + p2)
+                    .length()
+                    .max((p1 - p2 * 2.0 + p3).length());
+                (0.75 * dd / tolerance).sqrt()
+            }
+        };
+
+        let n = (n.ceil() as usize).clamp(1, MAX_FLATTEN_STEPS);
+        for i in 1..n {
+            f(self.eval(i as f32 / n as f32));
+        }
+
+        f(self.end());
+    }
+
+    /// Returns the arc length of the segment.
+    ///
+    /// Curves are subdivided until the control polygon and the chord lengths differ
+    /// by less than `tolerance`.
+    pub(crate) fn arc_length(&self, tolerance: f32) -> f32 {
+        match *self {
+            Segment::Line(p0, p1) => p0.distance(p1),
+            Segment::Quad(p0, p1, p2) => {
+                // Degree elevation gives the same curve as a cubic.
+                let c1 = p0.lerp(p1, 2.0 / 3.0);
+                let c2 = p2.lerp(p1, 2.0 / 3.0);
+                cubic_arc_length([p0, c1, c2, p2], tolerance.max(1e-4), 0)
+            }
+            Segment::Cubic(p0, p1, p2, p3) => {
+                cubic_arc_length([p0, p1, p2, p3], tolerance.max(1e-4), 0)
+            }
+        }
+    }
+
+    /// Calls `f` with every `t` in `(0, 1)` where the curve has a horizontal or
+    /// vertical tangent, i.e. an extremum along either axis.
+    pub(crate) fn for_each_extremum<F: FnMut(f32)>(&self, mut f: F) {
+        let mut emit = |t: f32| {
+            if t > 0.0 && t < 1.0 {
+                f(t);
+            }
+        };
+
+        match *self {
+            Segment::Line(..) => {}
+            Segment::Quad(p0, p1, p2) => {
+                for &(a, b, c) in &[(p0.x, p1.x, p2.x), (p0.y, p1.y, p2.y)] {
+                    let d = a - 2.0 * b + c;
+                    if d != 0.0 {
+                        emit((a - b) / d);
+                    }
+                }
+            }
+            Segment::Cubic(p0, p1, p2, p3) => {
+                for &(a, b, c, d) in &[(p0.x, p1.x, p2.x, p3.x), (p0.y, p1.y, p2.y, p3.y)] {
+                    // The roots of the derivative divided by 3.
+                    let qa = -a + 3.0 * b - 3.0 * c + d;
+                    let qb = 2.0 * (a - 2.0 * b + c);
+                    let qc = b - a;
+                    solve_quadratic(qa, qb, qc, &mut emit);
+                }
+            }
+        }
+    }
+}
+
+impl Segment {
+    /// Returns the direction at the start, ignoring control points that coincide with
+    /// the start point. Returns `None` for a segment without length.
+    pub(crate) fn start_tangent(&self) -> Option<Point> {
+        let p0 = self.start();
+        let points = match *self {
+            Segment::Line(_, p1) => [p1, p1, p1],
+            Segment::Quad(_, p1, p2) => [p1, p2, p2],
+            Segment::Cubic(_, p1, p2, p3) => [p1, p2, p3],
+        };
+        points.iter().find(|&&p| p != p0).map(|&p| p - p0)
+    }
+
+    /// Returns the direction at the end, ignoring control points that coincide with
+    /// the end point. Returns `None` for a segment without length.
+    pub(crate) fn end_tangent(&self) -> Option<Point> {
+        let p = self.end();
+        let points = match *self {
+            Segment::Line(p0, _) => [p0, p0, p0],
+            Segment::Quad(p0, p1, _) => [p1, p0, p0],
+            Segment::Cubic(p0, p1, p2, _) => [p2, p1, p0],
+        };
+        points.iter().find(|&&c| c != p).map(|&c| p - c)
+    }
+
+    /// Splits the segment at `t`.
+    pub(crate) fn split(&self, t: f32) -> (Segment, Segment) {
+        match *self {
+            Segment::Line(p0, p1) => {
+                let p = p0.lerp(p1, t);
+                (Segment::Line(p0, p), Segment::Line(p, p1))
+            }
+            Segment::Quad(p0, p1, p2) => {
+                let p01 = p0.lerp(p1, t);
+                let p12 = p1.lerp(p2, t);
+                let p = p01.lerp(p12, t);
+                (Segment::Quad(p0, p01, p), Segment::Quad(p, p12, p2))
+            }
+            Segment::Cubic(p0, p1, p2, p3) => {
+                let (a, b) = split_cubic(p0, p1, p2, p3, t);
+                (
+                    Segment::Cubic(a[0], a[1], a[2], a[3]),
+                    Segment::Cubic(b[0], b[1], b[2], b[3]),
+                )
+            }
+        }
+    }
+
+    /// Returns the part of the segment between `t0` and `t1`.
+    pub(crate) fn subsegment(&self, t0: f32, t1: f32) -> Segment {
+        let head = if t1 < 1.0 { self.split(t1).0 } else { *self };
+        if t0 > 0.0 && t1 > 0.0 {
+            head.split(t0 / t1).1
+        } else {
+            head
+        }
+    }
+
+    /// Calls `f` with every `t` in `(0, 1)` where the segment crosses the vertical
+    /// line at `value`, or the horizontal one when `vertical` is `false`.
+    pub(crate) fn for_each_crossing<F: FnMut(f32)>(&self, vertical: bool, value: f32, mut f: F) {
+        let coord = |t: f32| {
+            let p = self.eval(t);
+            if vertical {
+                p.x
+            } else {
+                p.y
+            }
+        };
+
+        // Split at the extrema so every piece is monotonic.
+        let mut ts = vec![0.0, 1.0];
+        self.for_each_extremum(|t| ts.push(t));
+        ts.sort_by(|a, b| a.total_cmp(b));
+        for w in ts.windows(2) {
+            let (mut lo, mut hi) = (w[0], w[1]);
+            let (v0, v1) = (coord(lo) - value, coord(hi) - value);
+            if v0 * v1 >= 0.0 {
+                continue;
+            }
+
+            for _ in 0..MAX_BISECTIONS {
+                let mid = (lo + hi) * 0.5;
+                if (coord(mid) - value) * v0 > 0.0 {
+                    lo = mid;
+                } else {
+                    hi = mid;
+                }
+            }
+
+            f((lo + hi) * 0.5);
+        }
+    }
+}
+
+fn cubic_arc_length(p: [Point; 4], tolerance: f32, depth: u32) -> f32 {
+    const MAX_DEPTH: u32 = 16;
+
+    let chord = p[0].distance(p[3]);
+    let polygon = p[0].distance(p[1]) + p[1].distance(p[2]) + p[2].distance(p[3]);
+    if polygon - chord <= tolerance || depth == MAX_DEPTH {
+        // Gravesen's estimate, which converges much faster than either length alone.
+        return (2.0 * chord + polygon) / 3.0;
+    }
+
+    let (a, b) = split_cubic(p[0], p[1], p[2], p[3], 0.5);
+    cubic_arc_length(a, tolerance * 0.5, depth + 1)
+        + cubic_arc_length(b, tolerance * 0.5, depth + 1)
+}
+
+/// Integrates `f` over `[0, 1]` using five point Gauss-Legendre quadrature, which
+/// is exact for polynomials up to degree 9.
+pub(crate) fn integrate<F: Fn(f32) -> f32>(f: F) -> f32 {
+    const NODES: [(f32, f32); 5] = [
+        (0.0, 0.568_888_9),
+        (-0.538_469_3, 0.478_628_67),
+        (0.538_469_3, 0.478_628_67),
+        (-0.906_179_85, 0.236_926_89),
+        (0.906_179_85, 0.236_926_89),
+    ];
+
+    NODES
+        .iter()
+        .map(|&(x, w)| w * f((x + 1.0) * 0.5))
+        .sum::<f32>()
+        * 0.5
+}
+
+/// Calls `f` with the real roots of `a * t^2 + b * t + c`.
+pub(crate) fn solve_quadratic<F: FnMut(f32)>(a: f32, b: f32, c: f32, mut f: F) {
+    const EPSILON: f32 = 1e-6;
+
+    if a.abs() < EPSILON {
+        if b.abs() >= EPSILON {
+            f(-c / b);
+        }
+
+        return;
+    }
+
+    let disc = b * b - 4.0 * a * c;
+    if disc < 0.0 {
+        return;
+    }
+
+    if disc == 0.0 {
+        f(-b / (2.0 * a));
+        return;
+    }
+
+    // Avoids the cancellation of the textbook formula.
+    let q = -0.5 * (b + b.signum() * disc.sqrt());
+    f(q / a);
+    if q != 0.0 {
+        f(c / q);
+    }
+}
+
+impl Contour {
+    /// Returns the line that closes the contour, unless it already ends at its start.
+    pub(crate) fn closing_line(&self) -> Option<Segment> {
+        let first = *self.points.first()?;
+        let last = *self.points.last()?;
+        if first != last {
+            Some(Segment::Line(last, first))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the contour flattened into a polyline within `tolerance`.
+    ///
+    /// For closed contours the closing line is implied, i.e. the last point is not
+    /// repeated.
+    pub(crate) fn flatten_points(&self, tolerance: f32) -> Vec<Point> {
+        let mut points: Vec<Point> = self.points.first().copied().into_iter().collect();
+        for segment in self.segments() {
+            segment.flatten(tolerance, |p| points.push(p));
+        }
+
+        if self.is_closed() && points.len() > 1 && points.first() == points.last() {
+            points.pop();
+        }
+
+        points
+    }
+
+    /// Replaces the contour with connected segments.
+    pub(crate) fn set_segments<I>(&mut self, start: Point, segments: I, closed: bool)
+    where
+        I: IntoIterator<Item = Segment>,
+    {
+        self.verbs.clear();
+        self.points.clear();
+        self.verbs.push(PathVerb::MoveTo);
+        self.points.push(start);
+        for segment in segments {
+            match segment {
+                Segment::Line(_, p) => {
+                    self.verbs.push(PathVerb::LineTo);
+                    self.points.push(p);
+                }
+                Segment::Quad(_, p1, p) => {
+                    self.verbs.push(PathVerb::QuadTo);
+                    self.points.extend(&[p1, p]);
+                }
+                Segment::Cubic(_, p1, p2, p) => {
+                    self.verbs.push(PathVerb::CurveTo);
+                    self.points.extend(&[p1, p2, p]);
+                }
+            }
+        }
+
+        if closed {
+            self.verbs.push(PathVerb::Close);
+        }
+    }
+
+    /// Returns the drawn segments of the contour.
+    ///
+    /// The implicit closing line from the last point back to the first one is not
+    /// included.
+    pub(crate) fn segments(&self) -> impl Iterator<Item = Segment> + '_ {
+        let mut points = self.points.iter().copied();
+        let mut last = Point::default();
+        self.verbs.iter().filter_map(move |v| match v {
+            PathVerb::MoveTo => {
+                last = points.next().unwrap();
+                None
+            }
+            PathVerb::LineTo => {
+                let p0 = last;
+                last = points.next().unwrap();
+                Some(Segment::Line(p0, last))
+            }
+            PathVerb::QuadTo => {
+                let p0 = last;
+                let p1 = points.next().unwrap();
+                last = points.next().unwrap();
+                Some(Segment::Quad(p0, p1, last))
+            }
+            PathVerb::CurveTo => {
+                let p0 = last;
+                let p1 = points.next().unwrap();
+                let p2 = points.next().unwrap();
+                last = points.next().unwrap();
+                Some(Segment::Cubic(p0, p1, p2, last))
+            }
+            PathVerb::Close => None,
+        })
+    }
+}
+
+/// Splits a cubic curve at `t`.
+pub(crate) fn split_cubic(
+    p0: Point,
+    p1: Point,
+    p2: Point,
+    p3: Point,
+    t: f32,
+) -> ([Point; 4], [Point; 4]) {
+    let p01 = p0.lerp(p1, t);
+    let p12 = p1.lerp(p2, t);
+    let p23 = p2.lerp(p3, t);
+    let p012 = p01.lerp(p12, t);
+    let p123 = p12.lerp(p23, t);
+    let p = p012.lerp(p123, t);
+    ([p0, p01, p012, p], [p, p123, p23, p3])
+}
+
+/// Approximates a cubic curve with quadratic ones.
+///
+/// Returns the control and end point of each quadratic curve. The cubic is split
+/// into an increasing number of equal parts until every part is within `tolerance`.
+pub(crate) fn cubic_to_quads(
+    p0: Point,
+    p1: Point,
+    p2: Point,
+    p3: Point,
+    tolerance: f32,
+) -> Vec<(Point, Point)> {
+    const MAX_SPLITS: usize = 16;
+
+    let mut quads = Vec::new();
+    for n in 1..=MAX_SPLITS {
+        quads.clear();
+        let mut within_tolerance = true;
+        let mut rest = [p0, p1, p2, p3];
+        for i in 0..n {
+            let piece = if i + 1 < n {
+                // Split off the first of the remaining `n - i` equal parts.
+                let (a, b) = split_cubic(rest[0], rest[1], rest[2], rest[3], 1.0 / (n - i) as f32);
+                rest = b;
+                a
+            } else {
+                rest
+            };
+
+            // The best single quadratic control point for the piece. The deviation of
+            // the degree elevated quadratic from the cubic peaks at `sqrt(3) / 6` of
+            // the control point difference.
+            let q = (piece[1] + piece[2]) * 0.75 - (piece[0] + piece[3]) * 0.25;
+            let e1 = piece[0].lerp(q, 2.0 / 3.0);
+            let err = piece[1].distance(e1) * (3f32.sqrt() / 6.0);
+            if err > tolerance {
+                within_tolerance = false;
+            }
+
+            quads.push((q, piece[3]));
+        }
+
+        if within_tolerance {
+            break;
+        }
+    }
+
+    quads
+}
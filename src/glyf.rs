@@ -0,0 +1,237 @@
+use crate::components::{
+    ARGS_ARE_XY_VALUES, ARG_1_AND_2_ARE_WORDS, MORE_COMPONENTS, USE_MY_METRICS,
+    WE_HAVE_AN_X_AND_Y_SCALE, WE_HAVE_A_SCALE, WE_HAVE_A_TWO_BY_TWO,
+};
+use crate::geom::{cubic_to_quads, Segment};
+use crate::Outline;
+
+const ON_CURVE_POINT: u8 = 0x01;
+const X_SHORT_VECTOR: u8 = 0x02;
+const Y_SHORT_VECTOR: u8 = 0x04;
+const REPEAT_FLAG: u8 = 0x08;
+const X_IS_SAME_OR_POSITIVE: u8 = 0x10;
+const Y_IS_SAME_OR_POSITIVE: u8 = 0x20;
+
+// The maximum distance in font units between a cubic curve and its quadratic approximation.
+const CUBIC_TOLERANCE: f32 = 1.0;
+
+impl Outline {
+    /// Returns the outline as a TrueType `glyf` table entry.
+    ///
+    /// Coordinates are rounded to integers and cubic curves are approximated with
+    /// quadratic ones. The entry has no instructions and is not padded. An empty
+    /// outline results in empty data, which is how `glyf` stores empty glyphs.
+    pub fn to_glyf_data(&self) -> Vec<u8> {
+        let mut end_points = Vec::new();
+        let mut points: Vec<(i16, i16, bool)> = Vec::new();
+        for c in &self.contours {
+            let start = match c.segments().next() {
+                Some(segment) => segment.start(),
+                None => continue,
+            };
+
+            let first = points.len();
+            points.push((round(start.x), round(start.y), true));
+            for segment in c.segments() {
+                match segment {
+                    Segment::Line(_, p) => points.push((round(p.x), round(p.y), true)),
+                    Segment::Quad(_, p1, p) => {
+                        points.push((round(p1.x), round(p1.y), false));
+                        points.push((round(p.x), round(p.y), true));
+                    }
+                    Segment::Cubic(p0, p1, p2, p3) => {
+                        for (q, p) in cubic_to_quads(p0, p1, p2, p3, CUBIC_TOLERANCE) {
+                            points.push((round(q.x), round(q.y), false));
+                            points.push((round(p.x), round(p.y), true));
+                        }
+                    }
+                }
+            }
+
+            // Contours are implicitly closed.
+            if points.len() - first > 1 && points.last() == points.get(first) {
+                points.pop();
+            }
+
+            end_points.push((points.len() - 1) as u16);
+        }
+
+        if points.is_empty() {
+            return Vec::new();
+        }
+
+        let mut data = Vec::new();
+        let (mut x_min, mut y_min, mut x_max, mut y_max) = (i16::MAX, i16::MAX, i16::MIN, i16::MIN);
+        for &(x, y, _) in &points {
+            x_min = x_min.min(x);
+            y_min = y_min.min(y);
+            x_max = x_max.max(x);
+            y_max = y_max.max(y);
+        }
+
+        data.extend_from_slice(&(end_points.len() as i16).to_be_bytes());
+        for n in &[x_min, y_min, x_max, y_max] {
+            data.extend_from_slice(&n.to_be_bytes());
+        }
+
+        for n in &end_points {
+            data.extend_from_slice(&n.to_be_bytes());
+        }
+
+        // No instructions.
+        data.extend_from_slice(&0u16.to_be_bytes());
+
+        let mut flags = Vec::with_capacity(points.len());
+        let mut xs = Vec::new();
+        let mut ys = Vec::new();
+        let (mut last_x, mut last_y) = (0i16, 0i16);
+        for &(x, y, on_curve) in &points {
+            let mut flag = if on_curve { ON_CURVE_POINT } else { 0 };
+            flag |= write_coord(
+                &mut xs,
+                x.wrapping_sub(last_x),
+                X_SHORT_VECTOR,
+                X_IS_SAME_OR_POSITIVE,
+            );
+            flag |= write_coord(
+                &mut ys,
+                y.wrapping_sub(last_y),
+                Y_SHORT_VECTOR,
+                Y_IS_SAME_OR_POSITIVE,
+            );
+            flags.push(flag);
+            last_x = x;
+            last_y = y;
+        }
+
+        let mut i = 0;
+        while i < flags.len() {
+            let flag = flags[i];
+            let mut repeat = 0;
+            while repeat < 255 && i + 1 + repeat < flags.len() && flags[i + 1 + repeat] == flag {
+                repeat += 1;
+            }
+
+            if repeat > 0 {
+                data.push(flag | REPEAT_FLAG);
+                data.push(repeat as u8);
+            } else {
+                data.push(flag);
+            }
+
+            i += 1 + repeat;
+        }
+
+        data.extend_from_slice(&xs);
+        data.extend_from_slice(&ys);
+        data
+    }
+
+    /// Returns the outline as a composite `glyf` table entry that refers to the glyphs
+    /// of its top level components, see `Outline::new_with_components`.
+    ///
+    /// Returns `None` when the outline has no components, has contours outside of
+    /// them or a component transform scales by 2 or more, which `glyf` can't store.
+    pub fn to_glyf_composite_data(&self) -> Option<Vec<u8>> {
+        let last = self.components.last()?;
+        let mut end = 0;
+        for node in &self.components {
+            if node.contours.start != end {
+                return None;
+            }
+
+            end = node.contours.end;
+        }
+
+        if last.contours.end != self.contours.len() {
+            return None;
+        }
+
+        let bbox = self.control_box();
+        let mut data = Vec::new();
+        data.extend_from_slice(&(-1i16).to_be_bytes());
+        for n in &[bbox.x_min, bbox.y_min, bbox.x_max, bbox.y_max] {
+            data.extend_from_slice(&round(*n).to_be_bytes());
+        }
+
+        for (i, node) in self.components.iter().enumerate() {
+            let component = &node.component;
+            let ts = component.transform;
+            let (arg1, arg2, mut flags) = match component.anchor {
+                Some((parent, child)) => (parent as i32, child as i32, 0),
+                None => (ts.e.round() as i32, ts.f.round() as i32, ARGS_ARE_XY_VALUES),
+            };
+
+            let words = if component.anchor.is_some() {
+                arg1 > 255 || arg2 > 255
+            } else {
+                [arg1, arg2].iter().any(|n| *n < -128 || *n > 127)
+            };
+            if words {
+                flags |= ARG_1_AND_2_ARE_WORDS;
+            }
+
+            let scale: &[f32] = if ts.b != 0.0 || ts.c != 0.0 {
+                flags |= WE_HAVE_A_TWO_BY_TWO;
+                &[ts.a, ts.b, ts.c, ts.d]
+            } else if ts.a != ts.d {
+                flags |= WE_HAVE_AN_X_AND_Y_SCALE;
+                &[ts.a, ts.d]
+            } else if ts.a != 1.0 {
+                flags |= WE_HAVE_A_SCALE;
+                &[ts.a]
+            } else {
+                &[]
+            };
+
+            if i + 1 < self.components.len() {
+                flags |= MORE_COMPONENTS;
+            }
+
+            if component.use_my_metrics {
+                flags |= USE_MY_METRICS;
+            }
+
+            data.extend_from_slice(&flags.to_be_bytes());
+            data.extend_from_slice(&component.glyph_id.0.to_be_bytes());
+            if words {
+                data.extend_from_slice(&(arg1 as i16).to_be_bytes());
+                data.extend_from_slice(&(arg2 as i16).to_be_bytes());
+            } else {
+                data.push(arg1 as u8);
+                data.push(arg2 as u8);
+            }
+
+            for n in scale {
+                if *n < -2.0 || *n >= 2.0 {
+                    return None;
+                }
+
+                data.extend_from_slice(&((n * 16384.0).round() as i16).to_be_bytes());
+            }
+        }
+
+        Some(data)
+    }
+}
+
+#[inline]
+fn round(n: f32) -> i16 {
+    n.round() as i16
+}
+
+fn write_coord(data: &mut Vec<u8>, delta: i16, short_flag: u8, same_or_positive_flag: u8) -> u8 {
+    if delta == 0 {
+        same_or_positive_flag
+    } else if delta.unsigned_abs() <= 255 {
+        data.push(delta.unsigned_abs() as u8);
+        if delta > 0 {
+            short_flag | same_or_positive_flag
+        } else {
+            short_flag
+        }
+    } else {
+        data.extend_from_slice(&delta.to_be_bytes());
+        0
+    }
+}
@@ -0,0 +1,92 @@
+use crate::geom::Segment;
+use crate::{Outline, Point};
+
+/// What `Outline::grid_fit` snaps to the pixel grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GridFitMode {
+    /// Only the heights of horizontal edges, e.g. the baseline, x-height and cap
+    /// height, with the points in between moved proportionally. This keeps the
+    /// shapes and the horizontal spacing intact.
+    Edges,
+    /// The heights of horizontal edges, and then every point along both axes.
+    AllPoints,
+}
+
+impl Outline {
+    /// Snaps the outline to the pixel grid at a given size, like light hinting.
+    ///
+    /// Horizontal edges are flat lines and the tops and bottoms of curves, and
+    /// snapping them makes them render sharply at small sizes. The outline stays in
+    /// font units, where a pixel is `units_per_em / ppem` units.
+    pub fn grid_fit(&mut self, ppem: f32, units_per_em: u16, mode: GridFitMode) {
+        if ppem <= 0.0 || units_per_em == 0 {
+            return;
+        }
+
+        let pixel = units_per_em as f32 / ppem;
+        let snap = |v: f32| (v / pixel).round() * pixel;
+
+        let mut edges: Vec<f32> = Vec::new();
+        for c in &self.contours {
+            for segment in c.segments().chain(c.closing_line()) {
+                if let Segment::Line(p0, p1) = segment {
+                    if p0.y == p1.y {
+                        edges.push(p0.y);
+                    }
+
+                    continue;
+                }
+
+                if matches!(segment.start_tangent(), Some(d) if is_horizontal(d)) {
+                    edges.push(segment.start().y);
+                }
+
+                if matches!(segment.end_tangent(), Some(d) if is_horizontal(d)) {
+                    edges.push(segment.end().y);
+                }
+
+                segment.for_each_extremum(|t| {
+                    if is_horizontal(segment.derivative(t)) {
+                        edges.push(segment.eval(t).y);
+                    }
+                });
+            }
+        }
+
+        edges.sort_by(|a, b| a.total_cmp(b));
+        edges.dedup();
+        let edges: Vec<(f32, f32)> = edges.into_iter().map(|y| (y, snap(y))).collect();
+        let fit = |y: f32| {
+            let i = edges.partition_point(|&(edge, _)| edge < y);
+            if i == 0 {
+                y + edges[0].1 - edges[0].0
+            } else if i == edges.len() {
+                y + edges[i - 1].1 - edges[i - 1].0
+            } else {
+                let (y0, s0) = edges[i - 1];
+                let (y1, s1) = edges[i];
+                s0 + (s1 - s0) * (y - y0) / (y1 - y0)
+            }
+        };
+
+        self.invalidate_bounds();
+        for c in &mut self.contours {
+            for p in &mut c.points {
+                if !edges.is_empty() {
+                    p.y = fit(p.y);
+                }
+
+                if mode == GridFitMode::AllPoints {
+                    p.x = snap(p.x);
+                    p.y = snap(p.y);
+                }
+            }
+        }
+    }
+}
+
+#[inline]
+fn is_horizontal(d: Point) -> bool {
+    d.y.abs() <= d.x.abs() * 1e-3
+}
@@ -0,0 +1,81 @@
+use image::{ImageBuffer, Pixel};
+
+use crate::{FillRule, Glyph, Raster, Transform};
+
+impl Raster {
+    /// Returns the raster as an image, e.g. a `GrayImage` or an `RgbaImage`, where
+    /// every pixel blends `color` over `background` by its coverage.
+    pub fn to_image<P>(&self, color: P, background: P) -> ImageBuffer<P, Vec<u8>>
+    where
+        P: Pixel<Subpixel = u8> + 'static,
+    {
+        let mut image = ImageBuffer::from_pixel(self.width, self.height, background);
+        for (p, &coverage) in image.pixels_mut().zip(&self.pixels) {
+            *p = blend(*p, color, coverage);
+        }
+
+        image
+    }
+}
+
+/// Renders glyphs in font units, e.g. a line of text from `outlines_for_str`, scaled
+/// by `scale` into an image that fits all of them, see `Raster::to_image`.
+///
+/// Each glyph is rasterized with the non-zero fill rule and blended over the image,
+/// so overlapping glyphs stay smooth. The image is empty when the glyphs have no
+/// outlines.
+pub fn render_glyphs<P>(
+    glyphs: &[Glyph],
+    scale: f32,
+    color: P,
+    background: P,
+) -> ImageBuffer<P, Vec<u8>>
+where
+    P: Pixel<Subpixel = u8> + 'static,
+{
+    let rasters: Vec<Raster> = glyphs
+        .iter()
+        .filter_map(|glyph| {
+            let outline = glyph.outline.as_ref()?;
+            let ts = Transform::from_translate(glyph.offset.0, glyph.offset.1)
+                .then(&Transform::from_scale(scale, scale));
+            Some(outline.rasterize(ts, FillRule::NonZero))
+        })
+        .filter(|raster| !raster.pixels.is_empty())
+        .collect();
+
+    let left = rasters.iter().map(|r| r.left).min().unwrap_or(0);
+    let top = rasters.iter().map(|r| r.top).max().unwrap_or(0);
+    let right = rasters
+        .iter()
+        .map(|r| r.left + r.width as i32)
+        .max()
+        .unwrap_or(0);
+    let bottom = rasters
+        .iter()
+        .map(|r| r.top - r.height as i32)
+        .min()
+        .unwrap_or(0);
+    let mut image =
+        ImageBuffer::from_pixel((right - left) as u32, (top - bottom) as u32, background);
+    for raster in &rasters {
+        let x = (raster.left - left) as u32;
+        let y = (top - raster.top) as u32;
+        for (i, row) in raster.pixels.chunks(raster.width as usize).enumerate() {
+            for (j, &coverage) in row.iter().enumerate() {
+                let p = image.get_pixel_mut(x + j as u32, y + i as u32);
+                *p = blend(*p, color, coverage);
+            }
+        }
+    }
+
+    image
+}
+
+#[inline]
+fn blend<P: Pixel<Subpixel = u8>>(background: P, color: P, coverage: u8) -> P {
+    let coverage = u32::from(coverage);
+    background.map2(&color, |b, c| {
+        ((u32::from(b) * (255 - coverage) + u32::from(c) * coverage + 127) / 255) as u8
+    })
+}
@@ -0,0 +1,100 @@
+use ttf_parser::{Face, GlyphId, Tag};
+
+use crate::parser::{read_fixed, read_u16};
+use crate::Outline;
+
+/// A named instance of a variable font, like "SemiBold Italic".
+#[derive(Debug, Clone, PartialEq)]
+pub struct NamedInstance {
+    /// The subfamily name.
+    pub name: String,
+    /// The value of every axis.
+    pub coordinates: Vec<(Tag, f32)>,
+}
+
+impl NamedInstance {
+    /// Returns the named instances from the `fvar` table of a face.
+    ///
+    /// Instances whose names aren't in the `name` table as Unicode are left out.
+    pub fn list(face: &Face) -> Vec<NamedInstance> {
+        parse_instances(face).unwrap_or_default()
+    }
+
+    /// Returns the named instance with the given name, ignoring ASCII case, or `None`
+    /// when the face has no such instance.
+    pub fn find(face: &Face, name: &str) -> Option<NamedInstance> {
+        Self::list(face)
+            .into_iter()
+            .find(|instance| instance.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Sets the coordinates of the instance on a face, so outlines built from it are
+    /// at this instance.
+    ///
+    /// Returns `None` when the face isn't variable or lacks one of the axes.
+    pub fn apply(&self, face: &mut Face) -> Option<()> {
+        for &(axis, value) in &self.coordinates {
+            face.set_variation(axis, value)?;
+        }
+
+        Some(())
+    }
+}
+
+impl Outline {
+    /// Returns a new outline at a named instance or `None` when the face has no such
+    /// instance, has no outline for the glyph or on error.
+    ///
+    /// See `NamedInstance` to look up the instance once and apply it to a face for
+    /// many glyphs.
+    pub fn new_with_named_instance(face: &Face, glyph_id: GlyphId, name: &str) -> Option<Self> {
+        let instance = NamedInstance::find(face, name)?;
+        Self::new_with_variations(face, glyph_id, &instance.coordinates)
+    }
+}
+
+fn parse_instances(face: &Face) -> Option<Vec<NamedInstance>> {
+    let data = face.table_data(Tag::from_bytes(b"fvar"))?;
+    let axes_offset = read_u16(data, 4)? as usize;
+    let axis_count = read_u16(data, 8)? as usize;
+    let axis_size = read_u16(data, 10)? as usize;
+    let instance_count = read_u16(data, 12)? as usize;
+    let instance_size = read_u16(data, 14)? as usize;
+    // Every instance has a name ID and flags before the coordinates.
+    if instance_size < 4 + axis_count * 4 {
+        return None;
+    }
+
+    let tags = (0..axis_count)
+        .map(|i| {
+            let offset = axes_offset + i * axis_size;
+            let tag = data.get(offset..offset + 4)?;
+            Some(Tag::from_bytes(&[tag[0], tag[1], tag[2], tag[3]]))
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    let instances_offset = axes_offset + axis_count * axis_size;
+    let mut instances = Vec::with_capacity(instance_count);
+    for i in 0..instance_count {
+        let offset = instances_offset + i * instance_size;
+        let name_id = read_u16(data, offset)?;
+        let coordinates = tags
+            .iter()
+            .enumerate()
+            .map(|(j, &tag)| {
+                let value = read_fixed(data, offset + 4 + j * 4)?;
+                Some((tag, value))
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        let name = face
+            .names()
+            .filter(|name| name.name_id() == name_id)
+            .find_map(|name| name.to_string());
+        if let Some(name) = name {
+            instances.push(NamedInstance { name, coordinates });
+        }
+    }
+
+    Some(instances)
+}
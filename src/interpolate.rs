@@ -0,0 +1,38 @@
+use crate::Outline;
+
+impl Outline {
+    /// Returns `true` if the outline can be interpolated with another one.
+    ///
+    /// This requires the same contours with the same sequence of lines and curves,
+    /// like the glyphs of compatible masters, e.g. the regular and bold faces of a
+    /// family that were designed together.
+    pub fn is_compatible_with(&self, other: &Outline) -> bool {
+        self.contours.len() == other.contours.len()
+            && self
+                .contours
+                .iter()
+                .zip(&other.contours)
+                .all(|(a, b)| a.verbs == b.verbs)
+    }
+
+    /// Returns the outline interpolated with another one or `None` when they aren't
+    /// compatible.
+    ///
+    /// `t` of zero gives this outline and one gives `other`. Values outside of that
+    /// range extrapolate, e.g. to get a bolder weight than the bold face.
+    pub fn interpolate_with(&self, other: &Outline, t: f32) -> Option<Outline> {
+        if !self.is_compatible_with(other) {
+            return None;
+        }
+
+        let mut outline = self.clone();
+        outline.invalidate_bounds();
+        for (c, other) in outline.contours.iter_mut().zip(&other.contours) {
+            for (p, q) in c.points.iter_mut().zip(&other.points) {
+                *p = p.lerp(*q, t);
+            }
+        }
+
+        Some(outline)
+    }
+}
@@ -0,0 +1,68 @@
+use crate::{write_number, Outline, PathVerb};
+
+impl Outline {
+    /// Returns the outline as JSON.
+    ///
+    /// The result is an object with the `bbox` and a `contours` array, where each
+    /// contour has a `verbs` array of SVG-like commands (`M`, `L`, `Q`, `C` and `Z`)
+    /// and a `points` array of `[x, y]` pairs consumed by those verbs in order.
+    pub fn to_json(&self) -> String {
+        let bbox = self.control_box();
+        let mut json = String::from("{\"bbox\":{\"x_min\":");
+        write_json_number(&mut json, bbox.x_min);
+        json.push_str(",\"y_min\":");
+        write_json_number(&mut json, bbox.y_min);
+        json.push_str(",\"x_max\":");
+        write_json_number(&mut json, bbox.x_max);
+        json.push_str(",\"y_max\":");
+        write_json_number(&mut json, bbox.y_max);
+        json.push_str("},\"contours\":[");
+        for (i, c) in self.contours.iter().enumerate() {
+            if i > 0 {
+                json.push(',');
+            }
+
+            json.push_str("{\"verbs\":[");
+            for (j, v) in c.verbs.iter().enumerate() {
+                if j > 0 {
+                    json.push(',');
+                }
+
+                json.push_str(match v {
+                    PathVerb::MoveTo => "\"M\"",
+                    PathVerb::LineTo => "\"L\"",
+                    PathVerb::QuadTo => "\"Q\"",
+                    PathVerb::CurveTo => "\"C\"",
+                    PathVerb::Close => "\"Z\"",
+                });
+            }
+
+            json.push_str("],\"points\":[");
+            for (j, p) in c.points.iter().enumerate() {
+                if j > 0 {
+                    json.push(',');
+                }
+
+                json.push('[');
+                write_json_number(&mut json, p.x);
+                json.push(',');
+                write_json_number(&mut json, p.y);
+                json.push(']');
+            }
+
+            json.push_str("]}");
+        }
+
+        json.push_str("]}");
+        json
+    }
+}
+
+fn write_json_number(s: &mut String, n: f32) {
+    // JSON has no representation for NaN and infinity.
+    if n.is_finite() {
+        write_number(s, n, None);
+    } else {
+        s.push_str("null");
+    }
+}
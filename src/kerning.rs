@@ -0,0 +1,113 @@
+use ttf_parser::{Face, GlyphId, Tag};
+
+use crate::otl::{coverage_index, feature_lookups, glyph_class};
+use crate::parser::{read_i16, read_u16};
+
+const PAIR_ADJUSTMENT: u16 = 2;
+const EXTENSION: u16 = 9;
+const X_ADVANCE: u16 = 0x0004;
+
+// Pair kerning from the `kern` feature of the `GPOS` table, or from the `kern` table
+// when the face has no such feature, like HarfBuzz does.
+pub(crate) struct Kerning<'a> {
+    face: &'a Face<'a>,
+    lookups: Vec<Vec<&'a [u8]>>,
+}
+
+impl<'a> Kerning<'a> {
+    pub(crate) fn new(face: &'a Face<'a>) -> Self {
+        let lookups = face
+            .table_data(Tag::from_bytes(b"GPOS"))
+            .map(|data| feature_lookups(data, &[Tag::from_bytes(b"kern")], EXTENSION))
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|lookup| lookup.kind == PAIR_ADJUSTMENT)
+            .map(|lookup| lookup.subtables)
+            .collect();
+        Kerning { face, lookups }
+    }
+
+    // Returns the adjustment of the advance of `left` when followed by `right`.
+    pub(crate) fn pair(&self, left: GlyphId, right: GlyphId) -> f32 {
+        if !self.lookups.is_empty() {
+            // Within a lookup the first subtable that covers the pair applies.
+            return self
+                .lookups
+                .iter()
+                .filter_map(|subtables| {
+                    subtables
+                        .iter()
+                        .find_map(|data| pair_adjustment(data, left, right))
+                })
+                .map(f32::from)
+                .sum();
+        }
+
+        self.face
+            .kerning_subtables()
+            .filter(|st| st.is_horizontal() && !st.is_variable() && !st.has_cross_stream())
+            .filter_map(|st| st.glyphs_kerning(left, right))
+            .map(f32::from)
+            .sum()
+    }
+}
+
+// Returns the horizontal advance adjustment of the first glyph from a pair adjustment
+// subtable, or `None` when it doesn't cover the pair.
+fn pair_adjustment(data: &[u8], left: GlyphId, right: GlyphId) -> Option<i16> {
+    let coverage = data.get(read_u16(data, 2)? as usize..)?;
+    let index = coverage_index(coverage, left)? as usize;
+    let format1 = read_u16(data, 4)?;
+    let format2 = read_u16(data, 6)?;
+    let size1 = format1.count_ones() as usize * 2;
+    let size2 = format2.count_ones() as usize * 2;
+    let record = match read_u16(data, 0)? {
+        1 => {
+            if index >= read_u16(data, 8)? as usize {
+                return None;
+            }
+
+            let offset = read_u16(data, 10 + index * 2)? as usize;
+            let pairs = data.get(offset..)?;
+            let record_size = 2 + size1 + size2;
+            let (mut lo, mut hi) = (0, read_u16(pairs, 0)? as usize);
+            loop {
+                if lo >= hi {
+                    return None;
+                }
+
+                let mid = (lo + hi) / 2;
+                let record = 2 + mid * record_size;
+                let glyph = read_u16(pairs, record)?;
+                if glyph == right.0 {
+                    break offset + record + 2;
+                } else if glyph < right.0 {
+                    lo = mid + 1;
+                } else {
+                    hi = mid;
+                }
+            }
+        }
+        2 => {
+            let classes1 = data.get(read_u16(data, 8)? as usize..)?;
+            let classes2 = data.get(read_u16(data, 10)? as usize..)?;
+            let class1 = glyph_class(classes1, left) as usize;
+            let class2 = glyph_class(classes2, right) as usize;
+            let count1 = read_u16(data, 12)? as usize;
+            let count2 = read_u16(data, 14)? as usize;
+            if class1 >= count1 || class2 >= count2 {
+                return None;
+            }
+
+            16 + (class1 * count2 + class2) * (size1 + size2)
+        }
+        _ => return None,
+    };
+
+    if format1 & X_ADVANCE == 0 {
+        return Some(0);
+    }
+
+    // The fields before the advance are the placements.
+    read_i16(data, record + (format1 & 0x0003).count_ones() as usize * 2)
+}
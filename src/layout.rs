@@ -0,0 +1,381 @@
+use std::ops::Range;
+
+use ttf_parser::{Face, GlyphId, Tag};
+
+use crate::kerning::Kerning;
+use crate::marks::MarkAttachment;
+use crate::substitution::SingleSubstitution;
+use crate::Outline;
+
+/// A glyph positioned on the baseline.
+#[derive(Debug, Clone)]
+pub struct Glyph {
+    /// The glyph id, which is the `.notdef` glyph for characters the face lacks.
+    pub id: GlyphId,
+    /// The outline, in font units and not positioned, or `None` for glyphs without
+    /// one, like spaces.
+    pub outline: Option<Outline>,
+    /// The horizontal advance, including the kerning with the next glyph, which is
+    /// zero for marks.
+    pub advance: f32,
+    /// The position of the glyph origin.
+    pub offset: (f32, f32),
+    /// The index of the face of the glyph in a `FaceChain`, or 0.
+    pub face_index: usize,
+}
+
+/// A line of laid out text.
+#[derive(Debug, Clone)]
+pub struct Line {
+    /// The glyphs, without trailing whitespace at wrapped lines.
+    pub glyphs: Vec<Glyph>,
+    /// The sum of the glyph advances.
+    pub width: f32,
+    /// The vertical position of the baseline, which is 0 for the first line and
+    /// negative below it.
+    pub baseline: f32,
+}
+
+/// A text direction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Direction {
+    /// Left to right, like Latin.
+    LeftToRight,
+    /// Right to left, like Hebrew and Arabic.
+    RightToLeft,
+}
+
+/// Options for the layout of text.
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LayoutOptions {
+    /// The space added after every glyph, negative to tighten the text.
+    pub letter_spacing: f32,
+    /// The space added after every whitespace character, on top of the letter
+    /// spacing.
+    pub word_spacing: f32,
+    /// The `GSUB` features to select glyphs with, like `smcp`, see
+    /// `SingleSubstitution`.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub features: Vec<Tag>,
+}
+
+/// Returns the glyphs of a string, one for each character, placed one after another
+/// on the baseline starting at the origin.
+///
+/// Characters are mapped to glyphs with the `cmap` table, without any shaping, so
+/// this suits simple scripts. Pairs are kerned with the `kern` feature of the `GPOS`
+/// table, or the `kern` table for fonts without it. Marks, like combining accents,
+/// are attached to the glyphs before them with the `mark` and `mkmk` features, see
+/// `MarkAttachment`. Translate each outline by its offset, or emit it with
+/// `Outline::emit_transformed`, to draw the text.
+///
+/// Use `outlines_for_runs` for letter and word spacing.
+pub fn outlines_for_str(face: &Face, text: &str) -> Vec<Glyph> {
+    outlines_for_runs(
+        face,
+        &[(text, Direction::LeftToRight)],
+        LayoutOptions::default(),
+    )
+}
+
+/// Returns the glyphs of runs of text in visual order, i.e. the first run is placed
+/// leftmost, see `outlines_for_str` for how the glyphs are placed.
+///
+/// The characters of right-to-left runs are placed from right to left. They aren't
+/// mirrored or shaped, so e.g. Arabic gets unjoined forms. With the `unicode-bidi`
+/// feature `outlines_for_bidi_str` finds the runs of a string.
+///
+/// The spacing of the options is added to the advances after kerning.
+pub fn outlines_for_runs(
+    face: &Face,
+    runs: &[(&str, Direction)],
+    options: LayoutOptions,
+) -> Vec<Glyph> {
+    let context = Context::new(face, options);
+    let mut glyphs = Vec::new();
+    let mut x = 0.0;
+    for &(text, direction) in runs {
+        let run = Run {
+            text,
+            direction,
+            y: 0.0,
+        };
+        context.push_run(run, &mut x, &mut glyphs);
+    }
+
+    glyphs
+}
+
+/// Returns the glyphs of a string with mixed directions, ordered by the Unicode
+/// Bidirectional Algorithm, see `outlines_for_runs`.
+#[cfg(feature = "unicode-bidi")]
+pub fn outlines_for_bidi_str(face: &Face, text: &str, options: LayoutOptions) -> Vec<Glyph> {
+    let runs: Vec<(&str, Direction)> = bidi_runs(text, 0..text.len())
+        .into_iter()
+        .map(|(range, direction)| (&text[range], direction))
+        .collect();
+    outlines_for_runs(face, &runs, options)
+}
+
+/// Returns the lines of a paragraph wrapped to a maximum width, see
+/// `outlines_for_str` for how the glyphs of each line are placed.
+///
+/// Lines are broken after whitespace, or within words that don't fit on a line of
+/// their own, and at line breaks in the text. Lines are spaced by the ascender minus
+/// the descender plus the line gap, from the `OS/2` or `hhea` table. The spacing of
+/// the options is taken into account when wrapping.
+pub fn layout_paragraph(
+    face: &Face,
+    text: &str,
+    max_width: f32,
+    options: LayoutOptions,
+) -> Vec<Line> {
+    layout_lines(face, text, max_width, options, |_, range| {
+        vec![(range, Direction::LeftToRight)]
+    })
+}
+
+/// Returns the lines of a paragraph with mixed directions wrapped to a maximum width,
+/// see `layout_paragraph`.
+///
+/// Lines are broken in logical order and the glyphs of each line are then ordered by
+/// the Unicode Bidirectional Algorithm.
+#[cfg(feature = "unicode-bidi")]
+pub fn layout_bidi_paragraph(
+    face: &Face,
+    text: &str,
+    max_width: f32,
+    options: LayoutOptions,
+) -> Vec<Line> {
+    layout_lines(face, text, max_width, options, bidi_runs)
+}
+
+// Lays out the lines with `runs` returning the byte ranges of the runs of a line, in
+// visual order, given the paragraph and the range of the line.
+fn layout_lines<F>(
+    face: &Face,
+    text: &str,
+    max_width: f32,
+    options: LayoutOptions,
+    runs: F,
+) -> Vec<Line>
+where
+    F: Fn(&str, Range<usize>) -> Vec<(Range<usize>, Direction)>,
+{
+    let context = Context::new(face, options);
+    let line_height = f32::from(face.height()) + f32::from(face.line_gap());
+    let mut lines = Vec::new();
+    for paragraph in text.lines() {
+        let mut offsets: Vec<usize> = paragraph.char_indices().map(|(i, _)| i).collect();
+        let chars: Vec<char> = paragraph.chars().collect();
+        let ids = context.glyph_ids(paragraph);
+        let advances = context.advances(paragraph, &ids);
+        offsets.push(paragraph.len());
+        for range in wrap(&chars, &advances, max_width) {
+            let baseline = -(lines.len() as f32) * line_height;
+            let mut glyphs = Vec::new();
+            let mut x = 0.0;
+            for (range, direction) in runs(paragraph, offsets[range.start]..offsets[range.end]) {
+                let run = Run {
+                    text: &paragraph[range],
+                    direction,
+                    y: baseline,
+                };
+                context.push_run(run, &mut x, &mut glyphs);
+            }
+
+            lines.push(Line {
+                glyphs,
+                width: x,
+                baseline,
+            });
+        }
+    }
+
+    lines
+}
+
+// Returns the byte ranges of the runs of a range of a string, in visual order.
+#[cfg(feature = "unicode-bidi")]
+fn bidi_runs(text: &str, range: Range<usize>) -> Vec<(Range<usize>, Direction)> {
+    let bidi = unicode_bidi::BidiInfo::new(text, None);
+    let mut runs = Vec::new();
+    for paragraph in &bidi.paragraphs {
+        let start = paragraph.range.start.max(range.start);
+        let end = paragraph.range.end.min(range.end);
+        if start >= end {
+            continue;
+        }
+
+        let (levels, visual_runs) = bidi.visual_runs(paragraph, start..end);
+        for run in visual_runs {
+            let direction = if levels[run.start].is_rtl() {
+                Direction::RightToLeft
+            } else {
+                Direction::LeftToRight
+            };
+            runs.push((run, direction));
+        }
+    }
+
+    runs
+}
+
+pub(crate) struct Run<'a> {
+    pub(crate) text: &'a str,
+    pub(crate) direction: Direction,
+    pub(crate) y: f32,
+}
+
+pub(crate) struct Context<'a> {
+    face: &'a Face<'a>,
+    kerning: Kerning<'a>,
+    marks: MarkAttachment<'a>,
+    substitution: SingleSubstitution<'a>,
+    options: LayoutOptions,
+}
+
+impl<'a> Context<'a> {
+    pub(crate) fn new(face: &'a Face<'a>, options: LayoutOptions) -> Self {
+        Context {
+            face,
+            kerning: Kerning::new(face),
+            marks: MarkAttachment::new(face),
+            substitution: SingleSubstitution::new(face, &options.features),
+            options,
+        }
+    }
+
+    // Places the glyphs of a run starting at `x`, which is moved to the end of the
+    // run.
+    pub(crate) fn push_run(&self, run: Run, x: &mut f32, glyphs: &mut Vec<Glyph>) {
+        let ids = self.glyph_ids(run.text);
+        let advances = self.advances(run.text, &ids);
+        let mut offsets = vec![(0.0, run.y); ids.len()];
+        let mut order: Vec<usize> = (0..ids.len()).collect();
+        if run.direction == Direction::RightToLeft {
+            order.reverse();
+        }
+
+        for &i in &order {
+            offsets[i].0 = *x;
+            *x += advances[i];
+        }
+
+        // Marks follow their bases in logical order, whatever the direction.
+        let mut base = None;
+        for i in 0..ids.len() {
+            if !self.marks.is_mark(ids[i]) {
+                base = Some(i);
+                continue;
+            }
+
+            let attachment = if i > 0 && Some(i - 1) != base {
+                self.marks
+                    .attach_to_mark(ids[i - 1], ids[i])
+                    .map(|d| (i - 1, d))
+            } else {
+                None
+            };
+
+            let attachment = attachment.or_else(|| {
+                let base = base?;
+                let d = self.marks.attach_to_base(ids[base], ids[i])?;
+                Some((base, d))
+            });
+
+            if let Some((j, (dx, dy))) = attachment {
+                offsets[i] = (offsets[j].0 + dx, offsets[j].1 + dy);
+            }
+        }
+
+        for i in order {
+            glyphs.push(Glyph {
+                id: ids[i],
+                outline: Outline::new(self.face, ids[i]),
+                advance: advances[i],
+                offset: offsets[i],
+                face_index: 0,
+            });
+        }
+    }
+
+    // Returns the advances of the glyphs of the characters in `text`, including
+    // kerning and spacing. Marks don't advance and are skipped when kerning.
+    fn advances(&self, text: &str, ids: &[GlyphId]) -> Vec<f32> {
+        ids.iter()
+            .zip(text.chars())
+            .enumerate()
+            .map(|(i, (&id, c))| {
+                if self.marks.is_mark(id) {
+                    return 0.0;
+                }
+
+                let mut advance = self.face.glyph_hor_advance(id).map_or(0.0, f32::from);
+                let next = ids[i + 1..].iter().find(|&&id| !self.marks.is_mark(id));
+                if let Some(&next) = next {
+                    advance += self.kerning.pair(id, next);
+                }
+
+                advance += self.options.letter_spacing;
+                if c.is_whitespace() {
+                    advance += self.options.word_spacing;
+                }
+
+                advance
+            })
+            .collect()
+    }
+
+    pub(crate) fn glyph_ids(&self, text: &str) -> Vec<GlyphId> {
+        text.chars()
+            .map(|c| {
+                let glyph_id = self.face.glyph_index(c).unwrap_or(GlyphId(0));
+                self.substitution.apply(glyph_id)
+            })
+            .collect()
+    }
+}
+
+// Returns the ranges of the characters of each line, greedily filling the lines.
+fn wrap(chars: &[char], advances: &[f32], max_width: f32) -> Vec<Range<usize>> {
+    let mut lines = Vec::new();
+    let mut start = 0;
+    // The end of the line when breaking at the last whitespace.
+    let mut last_break = None;
+    let mut x = 0.0;
+    let mut i = 0;
+    while i < chars.len() {
+        if chars[i].is_whitespace() {
+            // Trailing whitespace never overflows.
+            if i == start || !chars[i - 1].is_whitespace() {
+                last_break = Some(i);
+            }
+
+            x += advances[i];
+            i += 1;
+            continue;
+        }
+
+        if x + advances[i] > max_width && i > start {
+            let end = last_break.filter(|&end| end > start).unwrap_or(i);
+            lines.push(start..end);
+            start = end;
+            while start < chars.len() && chars[start].is_whitespace() {
+                start += 1;
+            }
+
+            last_break = None;
+            x = advances[start..i].iter().sum();
+            continue;
+        }
+
+        x += advances[i];
+        i += 1;
+    }
+
+    lines.push(start..chars.len());
+    lines
+}
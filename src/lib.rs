@@ -1,7 +1,93 @@
 //! `ttf-parser` utils.
 
+mod atlas;
+#[cfg(feature = "kurbo")]
+mod bez_path;
+mod bitmap;
+mod buffers;
+mod cache;
+mod canvas;
+mod cff;
+mod clip;
+mod colr;
+mod components;
+mod contours;
+mod curves;
+mod decoration;
+mod effects;
+mod fallback;
+mod geom;
+mod glyf;
+mod grid;
+#[cfg(feature = "image")]
+mod image_buffer;
+mod instances;
+mod interpolate;
+mod json;
+mod kerning;
+mod layout;
+#[cfg(feature = "lyon")]
+mod lyon;
+mod marks;
+#[cfg(feature = "zeno")]
+mod mask;
+mod measure;
+mod mesh;
+mod metrics;
+mod msdf;
+mod names;
+mod otl;
+mod overlap;
+mod parser;
+mod pdf;
+mod raster;
+mod raster_cache;
+mod sfd;
+mod simplify;
+#[cfg(feature = "tiny-skia")]
+mod skia;
+mod stroke;
+mod substitution;
+mod svg;
+mod synthesis;
+mod transform;
+mod vertical;
+mod warp;
+
+pub use atlas::{Atlas, AtlasGlyph, AtlasOptions, Packing};
+pub use bitmap::{BitmapFormat, BitmapGlyph, GlyphImage};
+pub use buffers::OutlineBuffers;
+pub use colr::{Color, ColorStop, CompositeMode, Extend, Paint};
+pub use components::{Component, OutlineComponent};
+pub use contours::{ContourNode, ContourOrdering, Winding, WindingConvention};
+pub use decoration::{Decoration, HighlightMode};
+pub use fallback::FaceChain;
+pub use grid::GridFitMode;
+#[cfg(feature = "image")]
+pub use image_buffer::render_glyphs;
+pub use instances::NamedInstance;
+#[cfg(feature = "unicode-bidi")]
+pub use layout::{layout_bidi_paragraph, outlines_for_bidi_str};
+pub use layout::{
+    layout_paragraph, outlines_for_runs, outlines_for_str, Direction, Glyph, LayoutOptions, Line,
+};
+pub use marks::MarkAttachment;
+pub use measure::{CurvatureSample, Side};
+pub use mesh::Mesh;
+pub use metrics::GlyphMetrics;
+pub use msdf::MsdfRaster;
+pub use raster::{MonoRaster, Raster, Span, Spans};
+pub use raster_cache::RasterCache;
+pub use stroke::{LineCap, LineJoin};
+pub use substitution::SingleSubstitution;
+pub use synthesis::{ObliquePivot, ScriptPosition};
+pub use transform::Transform;
+pub use vertical::{outlines_for_vertical_runs, VerticalOrientation};
+pub use warp::Envelope;
+
 /// A bounding box.
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BBox {
     /// Minimum X coordinate.
     pub x_min: f32,
@@ -26,6 +112,15 @@ impl BBox {
         self.y_max - self.y_min
     }
 
+    /// Returns the bbox center.
+    #[inline]
+    pub fn center(&self) -> (f32, f32) {
+        (
+            (self.x_min + self.x_max) * 0.5,
+            (self.y_min + self.y_max) * 0.5,
+        )
+    }
+
     /// Extend the bbox.
     #[inline]
     pub fn extend_by(&mut self, x: f32, y: f32) {
@@ -36,31 +131,126 @@ impl BBox {
     }
 }
 
+/// A rule that determines which points are inside an outline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FillRule {
+    /// A point is inside when the contours wind around it a non-zero number of times.
+    NonZero,
+    /// A point is inside when a ray from it crosses the contours an odd number of times.
+    EvenOdd,
+}
+
+impl Default for FillRule {
+    #[inline]
+    fn default() -> Self {
+        FillRule::NonZero
+    }
+}
+
 /// A glyph outline.
-#[derive(Debug, Clone)]
+///
+/// Use `Outline::default()` together with `move_to`, `line_to` etc. to build an
+/// outline from scratch.
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Outline {
-    bbox: std::cell::Cell<Option<BBox>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    control_box: std::cell::Cell<Option<BBox>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    ink_box: std::cell::Cell<Option<BBox>>,
     cff: bool,
     contours: Vec<Contour>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    components: Vec<OutlineComponent>,
 }
 
 impl Outline {
     /// Returns a new outline or `None` when the glyph has no outline or on error.
     pub fn new(face: &ttf_parser::Face, glyph_id: ttf_parser::GlyphId) -> Option<Self> {
-        let mut outline = Outline {
-            bbox: std::cell::Cell::new(None),
-            cff: face.has_table(ttf_parser::TableName::CompactFontFormat)
+        let mut recorder = OutlineRecorder::new(
+            face.has_table(ttf_parser::TableName::CompactFontFormat)
                 || face.has_table(ttf_parser::TableName::CompactFontFormat2),
+        );
+        let _ = face.outline_glyph(glyph_id, &mut recorder)?;
+        Some(recorder.finish())
+    }
+
+    /// Returns a new outline at the given variation axis values or `None` when the
+    /// face isn't variable, lacks one of the axes, has no outline for the glyph or on
+    /// error.
+    ///
+    /// The face itself is left as is. `new` uses the variations already set on the
+    /// face with `Face::set_variation`, so this is a shortcut for a single instance.
+    /// Outlines don't share any cached data, so ones from different instances never
+    /// mix.
+    pub fn new_with_variations(
+        face: &ttf_parser::Face,
+        glyph_id: ttf_parser::GlyphId,
+        variations: &[(ttf_parser::Tag, f32)],
+    ) -> Option<Self> {
+        let mut face = face.clone();
+        for &(axis, value) in variations {
+            face.set_variation(axis, value)?;
+        }
+
+        Self::new(&face, glyph_id)
+    }
+
+    #[inline]
+    fn empty(cff: bool) -> Self {
+        Outline {
+            control_box: std::cell::Cell::new(None),
+            ink_box: std::cell::Cell::new(None),
+            cff,
             contours: Vec::new(),
-        };
-        let mut outline_builder = OutlineBuilder::new(&mut outline);
-        let _ = face.outline_glyph(glyph_id, &mut outline_builder)?;
-        Some(outline)
+            components: Vec::new(),
+        }
+    }
+
+    /// Returns `true` if the outline has no contours.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.contours.is_empty()
+    }
+
+    /// Returns the number of contours.
+    #[inline]
+    pub fn num_contours(&self) -> usize {
+        self.contours.len()
+    }
+
+    /// Returns the number of points, including off-curve control points.
+    pub fn num_points(&self) -> usize {
+        self.contours.iter().map(|c| c.num_points()).sum()
+    }
+
+    /// Returns the number of lines and curves.
+    ///
+    /// See `Contour::num_segments` for details.
+    pub fn num_segments(&self) -> usize {
+        self.contours.iter().map(|c| c.num_segments()).sum()
+    }
+
+    /// Returns the contours.
+    #[inline]
+    pub fn contours(&self) -> &[Contour] {
+        &self.contours
     }
 
     /// Returns the outline bounding box.
+    #[deprecated(note = "use `control_box` or `ink_box` instead")]
     pub fn bbox(&self) -> BBox {
-        if let Some(bbox) = self.bbox.get() {
+        self.control_box()
+    }
+
+    /// Returns the control box, i.e. the bounding box of all points including the
+    /// off-curve control points.
+    ///
+    /// This is fast but can overestimate the bounds of curves, see `ink_box` for the
+    /// exact bounds.
+    pub fn control_box(&self) -> BBox {
+        if let Some(bbox) = self.control_box.get() {
             bbox
         } else {
             let mut bbox = BBox::default();
@@ -75,14 +265,35 @@ impl Outline {
                 }
             }
 
-            self.bbox.set(Some(bbox));
+            self.control_box.set(Some(bbox));
             bbox
         }
     }
 
+    #[inline]
+    fn invalidate_bounds(&self) {
+        self.control_box.set(None);
+        self.ink_box.set(None);
+    }
+
     /// Embolden the outline.
+    ///
+    /// A negative strength thins the outline instead, see `embolden_xy`.
     pub fn embolden(&mut self, strength: f32) {
-        self.bbox.set(None);
+        self.embolden_xy(strength, strength);
+    }
+
+    /// Embolden the outline with different horizontal and vertical strengths.
+    ///
+    /// This is like FreeType's `FT_Outline_EmboldenXY`, e.g. use a smaller
+    /// `x_strength` for condensed bold variants.
+    ///
+    /// Negative strengths thin the outline. Contours that collapse or turn inside out
+    /// in the process, e.g. thin strokes when thinning or small counters when
+    /// emboldening, are removed.
+    pub fn embolden_xy(&mut self, x_strength: f32, y_strength: f32) {
+        self.invalidate_bounds();
+        let areas: Vec<f32> = self.contours.iter().map(|c| c.signed_area()).collect();
         for c in &mut self.contours {
             let num_points = c.points.len();
             if num_points == 0 {
@@ -141,19 +352,26 @@ impl Outline {
                         } else {
                             (-(in_pt.y + out_pt.y), in_pt.x + out_pt.x)
                         };
-                        if (strength * q) <= (len * d) {
-                            Point::new(x * strength / d, y * strength / d)
+                        // Restrict the shift to better handle collapsing segments.
+                        let x = if (x_strength * q) <= (len * d) {
+                            x * x_strength / d
                         } else {
-                            Point::new(x * len / q, y * len / q)
-                        }
+                            x * len / q
+                        };
+                        let y = if (y_strength * q) <= (len * d) {
+                            y * y_strength / d
+                        } else {
+                            y * len / q
+                        };
+                        Point::new(x, y)
                     } else {
                         Point::default()
                     };
 
                     while i != j {
                         let pt = &mut c.points[i];
-                        pt.x += strength + shift_pt.x;
-                        pt.y += strength + shift_pt.y;
+                        pt.x += x_strength + shift_pt.x;
+                        pt.y += y_strength + shift_pt.y;
                         i = if i < last { i + 1 } else { 0 };
                     }
                 } else {
@@ -170,11 +388,27 @@ impl Outline {
                 c.points[num_points - 1] = *first;
             }
         }
+
+        let num_contours = self.contours.len();
+        let mut areas = areas.into_iter();
+        self.contours.retain(|c| {
+            let before = areas.next().unwrap();
+            let after = c.signed_area();
+            // Contours without area, like single points, are left alone.
+            before == 0.0 || ((before > 0.0) == (after > 0.0) && after != 0.0)
+        });
+        if self.contours.len() != num_contours {
+            self.components.clear();
+        }
     }
 
     /// Slant the outline.
+    ///
+    /// See `oblique_at` for slanting by an angle around other heights than the
+    /// baseline.
     pub fn oblique(&mut self, x_skew: f32) {
-        self.bbox.set(None);
+        self.invalidate_bounds();
+        self.transform_components(Transform::from_skew(x_skew, 0.0));
         for c in &mut self.contours {
             for p in &mut c.points {
                 if p.y != 0.0 {
@@ -184,9 +418,231 @@ impl Outline {
         }
     }
 
+    /// Apply an affine transform to the outline.
+    pub fn transform(&mut self, ts: Transform) {
+        self.invalidate_bounds();
+        self.transform_components(ts);
+        for c in &mut self.contours {
+            c.transform(ts);
+        }
+    }
+
+    /// Apply an affine transform to a single contour.
+    ///
+    /// Returns `None` when the contour index is out of bounds.
+    pub fn transform_contour(&mut self, index: usize, ts: Transform) -> Option<()> {
+        self.contours.get_mut(index)?.transform(ts);
+
+        self.invalidate_bounds();
+        Some(())
+    }
+
+    /// Scale the outline.
+    ///
+    /// Use `px / units_per_em` to convert from font units to pixels.
+    pub fn scale(&mut self, sx: f32, sy: f32) {
+        self.transform(Transform::from_scale(sx, sy));
+    }
+
+    /// Translate the outline.
+    pub fn translate(&mut self, dx: f32, dy: f32) {
+        for cache in &[&self.control_box, &self.ink_box] {
+            if let Some(mut bbox) = cache.get() {
+                bbox.x_min += dx;
+                bbox.y_min += dy;
+                bbox.x_max += dx;
+                bbox.y_max += dy;
+                cache.set(Some(bbox));
+            }
+        }
+
+        self.transform_components(Transform::from_translate(dx, dy));
+        for c in &mut self.contours {
+            for p in &mut c.points {
+                p.x += dx;
+                p.y += dy;
+            }
+        }
+    }
+
+    /// Normalize the outline from font units to a target em size.
+    ///
+    /// Scales the outline by `target_size / units_per_em`, e.g. pass the pixel size to
+    /// get pixel coordinates or `1.0` to get coordinates relative to the em.
+    pub fn normalize(&mut self, units_per_em: u16, target_size: f32) {
+        if units_per_em == 0 {
+            return;
+        }
+
+        let scale = target_size / units_per_em as f32;
+        self.scale(scale, scale);
+    }
+
+    /// Scale and translate the outline to fit into a rectangle.
+    ///
+    /// When `preserve_aspect` is set the outline is scaled uniformly and centered
+    /// within the rectangle. Returns the applied transform.
+    pub fn fit_into(&mut self, rect: BBox, preserve_aspect: bool) -> Transform {
+        let bbox = self.control_box();
+        let sx = if bbox.width() > 0.0 {
+            Some(rect.width() / bbox.width())
+        } else {
+            None
+        };
+        let sy = if bbox.height() > 0.0 {
+            Some(rect.height() / bbox.height())
+        } else {
+            None
+        };
+        let (sx, sy) = if preserve_aspect {
+            let s = match (sx, sy) {
+                (Some(sx), Some(sy)) => sx.min(sy),
+                (Some(s), None) | (None, Some(s)) => s,
+                (None, None) => 1.0,
+            };
+            (s, s)
+        } else {
+            (sx.unwrap_or(1.0), sy.unwrap_or(1.0))
+        };
+
+        let (cx, cy) = bbox.center();
+        let (rcx, rcy) = rect.center();
+        let ts = Transform::from_translate(-cx, -cy)
+            .then(&Transform::from_scale(sx, sy))
+            .then(&Transform::from_translate(rcx, rcy));
+        self.transform(ts);
+        ts
+    }
+
+    /// Rotate the outline around a pivot point.
+    ///
+    /// The angle is in radians. Use `(0.0, 0.0)` to rotate around the origin or
+    /// `outline.control_box().center()` to rotate around the bbox center.
+    pub fn rotate(&mut self, angle: f32, pivot: (f32, f32)) {
+        self.transform(Transform::from_rotate_at(angle, pivot.0, pivot.1));
+    }
+
+    /// Mirror the outline horizontally around its bbox center.
+    ///
+    /// The contour directions are reversed so the outline keeps filling correctly.
+    pub fn flip_horizontal(&mut self) {
+        let (x, _) = self.control_box().center();
+        self.flip_horizontal_at(x);
+    }
+
+    /// Mirror the outline horizontally around the vertical axis at `x`.
+    pub fn flip_horizontal_at(&mut self, x: f32) {
+        self.transform(Transform::new(-1.0, 0.0, 0.0, 1.0, 2.0 * x, 0.0));
+        for c in &mut self.contours {
+            c.reverse();
+        }
+    }
+
+    /// Mirror the outline vertically around its bbox center.
+    ///
+    /// The contour directions are reversed so the outline keeps filling correctly.
+    pub fn flip_vertical(&mut self) {
+        let (_, y) = self.control_box().center();
+        self.flip_vertical_at(y);
+    }
+
+    /// Mirror the outline vertically around the horizontal axis at `y`.
+    pub fn flip_vertical_at(&mut self, y: f32) {
+        self.transform(Transform::new(1.0, 0.0, 0.0, -1.0, 0.0, 2.0 * y));
+        for c in &mut self.contours {
+            c.reverse();
+        }
+    }
+
+    /// Convert the outline to a y-down coordinate system.
+    ///
+    /// Points are flipped and offset so that `ascent` ends up at `y = 0`, which is
+    /// what most raster and canvas APIs expect. The ascent must be in the same units
+    /// as the outline, i.e. scale it as well if the outline was scaled.
+    pub fn flip_y(&mut self, ascent: f32) {
+        self.transform(Transform::new(1.0, 0.0, 0.0, -1.0, 0.0, ascent));
+    }
+
+    /// Start a new contour at the given point.
+    pub fn move_to(&mut self, x: f32, y: f32) {
+        self.invalidate_bounds();
+        self.contours.push(Contour {
+            verbs: vec![PathVerb::MoveTo],
+            points: vec![Point::new(x, y)],
+            source_index: self.contours.len(),
+        });
+    }
+
+    /// Add a line to the current contour.
+    pub fn line_to(&mut self, x: f32, y: f32) {
+        let c = self.open_contour();
+        c.verbs.push(PathVerb::LineTo);
+        c.points.push(Point::new(x, y));
+    }
+
+    /// Add a quadratic curve to the current contour.
+    pub fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let c = self.open_contour();
+        c.verbs.push(PathVerb::QuadTo);
+        c.points.push(Point::new(x1, y1));
+        c.points.push(Point::new(x, y));
+    }
+
+    /// Add a cubic curve to the current contour.
+    pub fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        let c = self.open_contour();
+        c.verbs.push(PathVerb::CurveTo);
+        c.points.push(Point::new(x1, y1));
+        c.points.push(Point::new(x2, y2));
+        c.points.push(Point::new(x, y));
+    }
+
+    /// Close the current contour.
+    pub fn close(&mut self) {
+        if let Some(c) = self.contours.last_mut() {
+            if !c.is_closed() {
+                c.verbs.push(PathVerb::Close);
+            }
+        }
+    }
+
+    // Returns the last contour, or starts a new one when it's closed. Like in SVG a
+    // new contour starts where the closed one started.
+    fn open_contour(&mut self) -> &mut Contour {
+        self.invalidate_bounds();
+        match self.contours.last() {
+            Some(c) if !c.is_closed() => {}
+            last => {
+                let start = last.and_then(|c| c.points.first().copied());
+                let start = start.unwrap_or_default();
+                self.move_to(start.x, start.y);
+            }
+        }
+
+        self.contours.last_mut().unwrap()
+    }
+
     /// Emit the outline segments.
     pub fn emit(&self, builder: &mut dyn ttf_parser::OutlineBuilder) {
-        let mut points = self.contours.iter().flat_map(|c| &c.points);
+        self.emit_with(builder, |p| p);
+    }
+
+    /// Emit the outline segments with a transform applied to each point.
+    ///
+    /// The outline itself is left untouched, so a single outline can be emitted
+    /// at many sizes without being cloned.
+    pub fn emit_transformed(&self, ts: Transform, builder: &mut dyn ttf_parser::OutlineBuilder) {
+        self.emit_with(builder, |p| {
+            let (x, y) = ts.apply(p.x, p.y);
+            Point::new(x, y)
+        });
+    }
+
+    fn emit_with<F>(&self, builder: &mut dyn ttf_parser::OutlineBuilder, f: F)
+    where
+        F: Fn(Point) -> Point,
+    {
+        let mut points = self.contours.iter().flat_map(|c| &c.points).map(|p| f(*p));
         for v in self.contours.iter().flat_map(|c| &c.verbs) {
             match v {
                 PathVerb::MoveTo => {
@@ -216,22 +672,175 @@ impl Outline {
     }
 }
 
+/// A single closed or open contour of an outline.
 #[derive(Debug, Default, Clone)]
-struct Contour {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "ContourData"))]
+pub struct Contour {
+    verbs: Vec<PathVerb>,
+    points: Vec<Point>,
+    source_index: usize,
+}
+
+// A contour as deserialized, before it's checked.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct ContourData {
     verbs: Vec<PathVerb>,
     points: Vec<Point>,
+    #[serde(default)]
+    source_index: usize,
+}
+
+#[cfg(feature = "serde")]
+impl std::convert::TryFrom<ContourData> for Contour {
+    type Error = &'static str;
+
+    fn try_from(data: ContourData) -> Result<Self, Self::Error> {
+        Contour::from_parts(data.verbs, data.points, data.source_index)
+            .ok_or("contour doesn't start with a move or its points don't match its verbs")
+    }
 }
 
-#[derive(Debug, Clone, Copy)]
+impl Contour {
+    // Returns a contour or `None` when it doesn't start with a move or the number of
+    // points doesn't match the verbs, which the rest of the crate relies on.
+    fn from_parts(verbs: Vec<PathVerb>, points: Vec<Point>, source_index: usize) -> Option<Self> {
+        let num_points: usize = verbs.iter().map(|v| v.num_points()).sum();
+        if verbs.first() != Some(&PathVerb::MoveTo) || points.len() != num_points {
+            return None;
+        }
+
+        Some(Contour {
+            verbs,
+            points,
+            source_index,
+        })
+    }
+
+    /// Returns `true` if the contour has no lines or curves.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.num_segments() == 0
+    }
+
+    /// Returns the number of points, including off-curve control points.
+    #[inline]
+    pub fn num_points(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Returns the number of lines and curves.
+    ///
+    /// The line that implicitly closes a closed contour is not counted.
+    pub fn num_segments(&self) -> usize {
+        self.verbs
+            .iter()
+            .filter(|v| !matches!(v, PathVerb::MoveTo | PathVerb::Close))
+            .count()
+    }
+
+    /// Returns `true` if the contour ends with a close command.
+    #[inline]
+    pub fn is_closed(&self) -> bool {
+        matches!(self.verbs.last(), Some(PathVerb::Close))
+    }
+
+    fn transform(&mut self, ts: Transform) {
+        for p in &mut self.points {
+            let (x, y) = ts.apply(p.x, p.y);
+            p.x = x;
+            p.y = y;
+        }
+    }
+
+    fn reverse(&mut self) {
+        let mut verbs = Vec::with_capacity(self.verbs.len());
+        let mut points = Vec::with_capacity(self.points.len());
+        let mut start_verb = 0;
+        let mut start_point = 0;
+        let mut end_point = 0;
+        for (i, v) in self.verbs.iter().enumerate() {
+            if i > start_verb && matches!(v, PathVerb::MoveTo) {
+                reverse_subpath(
+                    &self.verbs[start_verb..i],
+                    &self.points[start_point..end_point],
+                    &mut verbs,
+                    &mut points,
+                );
+                start_verb = i;
+                start_point = end_point;
+            }
+
+            end_point += v.num_points();
+        }
+
+        reverse_subpath(
+            &self.verbs[start_verb..],
+            &self.points[start_point..end_point],
+            &mut verbs,
+            &mut points,
+        );
+        self.verbs = verbs;
+        self.points = points;
+    }
+}
+
+fn reverse_subpath(
+    verbs: &[PathVerb],
+    points: &[Point],
+    out_verbs: &mut Vec<PathVerb>,
+    out_points: &mut Vec<Point>,
+) {
+    if verbs.is_empty() {
+        return;
+    }
+
+    // Reversing the points also reverses the control points within each segment.
+    let closed = matches!(verbs.last(), Some(PathVerb::Close));
+    out_verbs.push(PathVerb::MoveTo);
+    out_verbs.extend(
+        verbs
+            .iter()
+            .rev()
+            .filter(|v| !matches!(v, PathVerb::MoveTo | PathVerb::Close)),
+    );
+    if closed {
+        out_verbs.push(PathVerb::Close);
+    }
+
+    out_points.extend(points.iter().rev());
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 enum PathVerb {
-    MoveTo,
-    LineTo,
-    QuadTo,
-    CurveTo,
-    Close,
+    #[cfg_attr(feature = "serde", serde(rename = "M"))]
+    MoveTo = 0,
+    #[cfg_attr(feature = "serde", serde(rename = "L"))]
+    LineTo = 1,
+    #[cfg_attr(feature = "serde", serde(rename = "Q"))]
+    QuadTo = 2,
+    #[cfg_attr(feature = "serde", serde(rename = "C"))]
+    CurveTo = 3,
+    #[cfg_attr(feature = "serde", serde(rename = "Z"))]
+    Close = 4,
+}
+
+impl PathVerb {
+    #[inline]
+    fn num_points(self) -> usize {
+        match self {
+            PathVerb::MoveTo | PathVerb::LineTo => 1,
+            PathVerb::QuadTo => 2,
+            PathVerb::CurveTo => 3,
+            PathVerb::Close => 0,
+        }
+    }
 }
 
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct Point {
     x: f32,
     y: f32,
@@ -244,60 +853,79 @@ impl Point {
     }
 }
 
-struct OutlineBuilder<'a> {
-    outline: &'a mut Outline,
-    current_contour: usize,
+/// Records outline segments into an [`Outline`].
+///
+/// Useful for capturing outlines from other sources than `ttf_parser::Face`, like a
+/// shaper or a custom glyph interpreter.
+#[derive(Debug, Clone)]
+pub struct OutlineRecorder {
+    outline: Outline,
 }
 
-impl<'a> OutlineBuilder<'a> {
+impl OutlineRecorder {
+    /// Returns a new recorder.
+    ///
+    /// Set `cff` when the recorded contours use the PostScript direction, i.e. outer
+    /// contours are counter-clockwise, which is needed by [`Outline::embolden`].
     #[inline]
-    fn new(outline: &'a mut Outline) -> Self {
+    pub fn new(cff: bool) -> Self {
         Self {
-            outline,
-            current_contour: 1,
+            outline: Outline::empty(cff),
         }
     }
 
+    /// Returns the recorded outline.
     #[inline]
-    fn current_contour(&mut self) -> &mut Contour {
-        if self.current_contour > self.outline.contours.len() {
-            self.outline.contours.push(Contour::default());
-        }
-
-        &mut self.outline.contours[self.current_contour - 1]
+    pub fn finish(self) -> Outline {
+        self.outline
     }
 }
 
-impl<'a> ttf_parser::OutlineBuilder for OutlineBuilder<'a> {
+impl ttf_parser::OutlineBuilder for OutlineRecorder {
+    #[inline]
     fn move_to(&mut self, x: f32, y: f32) {
-        let c = self.current_contour();
-        c.verbs.push(PathVerb::MoveTo);
-        c.points.push(Point::new(x, y));
+        self.outline.move_to(x, y);
     }
 
+    #[inline]
     fn line_to(&mut self, x: f32, y: f32) {
-        let c = self.current_contour();
-        c.verbs.push(PathVerb::LineTo);
-        c.points.push(Point::new(x, y));
+        self.outline.line_to(x, y);
     }
 
+    #[inline]
     fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
-        let c = self.current_contour();
-        c.verbs.push(PathVerb::QuadTo);
-        c.points.push(Point::new(x1, y1));
-        c.points.push(Point::new(x, y));
+        self.outline.quad_to(x1, y1, x, y);
     }
 
+    #[inline]
     fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
-        let c = self.current_contour();
-        c.verbs.push(PathVerb::CurveTo);
-        c.points.push(Point::new(x1, y1));
-        c.points.push(Point::new(x2, y2));
-        c.points.push(Point::new(x, y));
+        self.outline.curve_to(x1, y1, x2, y2, x, y);
     }
 
+    #[inline]
     fn close(&mut self) {
-        self.current_contour().verbs.push(PathVerb::Close);
-        self.current_contour += 1;
+        self.outline.close();
+    }
+}
+
+/// Writes a number without trailing zeros, optionally rounded to `precision` decimals.
+pub(crate) fn write_number(s: &mut String, n: f32, precision: Option<usize>) {
+    use std::fmt::Write;
+
+    let start = s.len();
+    match precision {
+        Some(precision) => {
+            write!(s, "{:.*}", precision, n).unwrap();
+            if s[start..].contains('.') {
+                let trimmed = s.trim_end_matches('0').trim_end_matches('.').len();
+                s.truncate(trimmed);
+            }
+        }
+        None => write!(s, "{}", n).unwrap(),
+    }
+
+    if &s[start..] == "-0" {
+        s.truncate(start);
+        s.push('0');
     }
 }
@@ -0,0 +1,146 @@
+use lyon_path::math::point;
+
+use crate::Outline;
+
+impl Outline {
+    /// Returns the outline as a `lyon_path::Path`.
+    pub fn to_lyon_path(&self) -> lyon_path::Path {
+        let mut builder = LyonPathBuilder {
+            builder: lyon_path::Path::builder(),
+            open: false,
+        };
+        self.emit(&mut builder);
+        builder.finish()
+    }
+}
+
+impl From<&Outline> for lyon_path::Path {
+    #[inline]
+    fn from(outline: &Outline) -> Self {
+        outline.to_lyon_path()
+    }
+}
+
+struct LyonPathBuilder {
+    builder: lyon_path::path::Builder,
+    open: bool,
+}
+
+impl LyonPathBuilder {
+    fn finish(mut self) -> lyon_path::Path {
+        if self.open {
+            self.builder.end(false);
+        }
+
+        self.builder.build()
+    }
+}
+
+impl ttf_parser::OutlineBuilder for LyonPathBuilder {
+    fn move_to(&mut self, x: f32, y: f32) {
+        // lyon requires every sub-path to be ended before a new one begins.
+        if self.open {
+            self.builder.end(false);
+        }
+
+        self.builder.begin(point(x, y));
+        self.open = true;
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.builder.line_to(point(x, y));
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        self.builder.quadratic_bezier_to(point(x1, y1), point(x, y));
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        self.builder
+            .cubic_bezier_to(point(x1, y1), point(x2, y2), point(x, y));
+    }
+
+    fn close(&mut self) {
+        if self.open {
+            self.builder.end(true);
+            self.open = false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lyon_path::math::point;
+    use lyon_path::PathEvent;
+
+    use crate::{Outline, PathVerb};
+
+    #[test]
+    fn events_match_outline() {
+        let mut outline = Outline::default();
+        outline.move_to(0.0, 0.0);
+        outline.line_to(10.0, 0.0);
+        outline.quad_to(15.0, 5.0, 10.0, 10.0);
+        outline.curve_to(5.0, 15.0, 0.0, 15.0, 0.0, 10.0);
+        outline.close();
+        outline.move_to(20.0, 0.0);
+        outline.line_to(30.0, 0.0);
+        outline.quad_to(35.0, 5.0, 30.0, 10.0);
+
+        let mut expected = Vec::new();
+        for c in outline.contours() {
+            let mut points = c.points.iter().map(|p| point(p.x, p.y));
+            let first = points.next().unwrap();
+            let mut last = first;
+            expected.push(PathEvent::Begin { at: first });
+            for v in &c.verbs[1..] {
+                let event = match v {
+                    PathVerb::LineTo => PathEvent::Line {
+                        from: last,
+                        to: points.next().unwrap(),
+                    },
+                    PathVerb::QuadTo => PathEvent::Quadratic {
+                        from: last,
+                        ctrl: points.next().unwrap(),
+                        to: points.next().unwrap(),
+                    },
+                    PathVerb::CurveTo => PathEvent::Cubic {
+                        from: last,
+                        ctrl1: points.next().unwrap(),
+                        ctrl2: points.next().unwrap(),
+                        to: points.next().unwrap(),
+                    },
+                    PathVerb::MoveTo | PathVerb::Close => continue,
+                };
+                last = event.to();
+                expected.push(event);
+            }
+
+            expected.push(PathEvent::End {
+                last,
+                first,
+                close: c.is_closed(),
+            });
+        }
+
+        let events: Vec<PathEvent> = outline.to_lyon_path().iter().collect();
+        assert_eq!(events, expected);
+        assert_eq!(events.len(), 9);
+        assert_eq!(
+            events[4],
+            PathEvent::End {
+                last: point(0.0, 10.0),
+                first: point(0.0, 0.0),
+                close: true,
+            }
+        );
+        assert_eq!(
+            events[8],
+            PathEvent::End {
+                last: point(30.0, 10.0),
+                first: point(20.0, 0.0),
+                close: false,
+            }
+        );
+    }
+}
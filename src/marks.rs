@@ -0,0 +1,157 @@
+use ttf_parser::{Face, GlyphId, Tag};
+
+use crate::otl::{coverage_index, feature_lookups, glyph_class, Lookup};
+use crate::parser::{read_i16, read_u16};
+
+const MARK_TO_BASE: u16 = 4;
+const MARK_TO_LIGATURE: u16 = 5;
+const MARK_TO_MARK: u16 = 6;
+const EXTENSION: u16 = 9;
+const MARK_GLYPH: u16 = 3;
+
+/// Mark attachment from the `mark` and `mkmk` features of the `GPOS` table.
+///
+/// Marks are positioned by aligning an anchor point of the mark with an anchor point
+/// of the glyph it attaches to.
+pub struct MarkAttachment<'a> {
+    classes: Option<&'a [u8]>,
+    bases: Vec<Lookup<'a>>,
+    marks: Vec<Lookup<'a>>,
+}
+
+impl<'a> MarkAttachment<'a> {
+    /// Returns the mark attachment of a face, which attaches nothing when the face has
+    /// no `GPOS` table.
+    pub fn new(face: &Face<'a>) -> Self {
+        let classes = face
+            .table_data(Tag::from_bytes(b"GDEF"))
+            .and_then(|data| {
+                let offset = read_u16(data, 4).filter(|&offset| offset != 0)?;
+                data.get(offset as usize..)
+            })
+            .filter(|data| data.len() > 2);
+        let (bases, marks) = match face.table_data(Tag::from_bytes(b"GPOS")) {
+            Some(data) => (
+                feature_lookups(data, &[Tag::from_bytes(b"mark")], EXTENSION),
+                feature_lookups(data, &[Tag::from_bytes(b"mkmk")], EXTENSION),
+            ),
+            None => (Vec::new(), Vec::new()),
+        };
+
+        MarkAttachment {
+            classes,
+            bases: bases
+                .into_iter()
+                .filter(|l| l.kind == MARK_TO_BASE || l.kind == MARK_TO_LIGATURE)
+                .collect(),
+            marks: marks
+                .into_iter()
+                .filter(|l| l.kind == MARK_TO_MARK)
+                .collect(),
+        }
+    }
+
+    /// Returns `true` if the glyph is a mark according to the `GDEF` table.
+    pub fn is_mark(&self, glyph_id: GlyphId) -> bool {
+        matches!(self.classes, Some(data) if glyph_class(data, glyph_id) == MARK_GLYPH)
+    }
+
+    /// Returns the offset from the origin of a base glyph to the origin of a mark
+    /// attached to it, or `None` when the mark doesn't attach to the glyph.
+    ///
+    /// Marks attach to the last component of ligatures.
+    pub fn attach_to_base(&self, base: GlyphId, mark: GlyphId) -> Option<(f32, f32)> {
+        self.bases.iter().find_map(|lookup| {
+            lookup.subtables.iter().find_map(|data| {
+                if lookup.kind == MARK_TO_BASE {
+                    attach(data, base, mark, base_anchor)
+                } else {
+                    attach(data, base, mark, ligature_anchor)
+                }
+            })
+        })
+    }
+
+    /// Returns the offset from the origin of a mark to the origin of another mark
+    /// attached to it, or `None` when the second mark doesn't attach to the first.
+    pub fn attach_to_mark(&self, base: GlyphId, mark: GlyphId) -> Option<(f32, f32)> {
+        self.marks.iter().find_map(|lookup| {
+            lookup
+                .subtables
+                .iter()
+                .find_map(|data| attach(data, base, mark, base_anchor))
+        })
+    }
+}
+
+impl std::fmt::Debug for MarkAttachment<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("MarkAttachment")
+            .field("has_classes", &self.classes.is_some())
+            .field("num_base_lookups", &self.bases.len())
+            .field("num_mark_lookups", &self.marks.len())
+            .finish()
+    }
+}
+
+// Returns the offset between the anchors of a mark attachment subtable. All three
+// lookup types share the layout of the subtable, only the base array differs, which
+// `anchor` looks up given the array, the base index, the mark class and class count.
+fn attach<F>(data: &[u8], base: GlyphId, mark: GlyphId, anchor: F) -> Option<(f32, f32)>
+where
+    F: Fn(&[u8], usize, usize, usize) -> Option<usize>,
+{
+    if read_u16(data, 0)? != 1 {
+        return None;
+    }
+
+    let mark_coverage = data.get(read_u16(data, 2)? as usize..)?;
+    let base_coverage = data.get(read_u16(data, 4)? as usize..)?;
+    let mark_index = coverage_index(mark_coverage, mark)? as usize;
+    let base_index = coverage_index(base_coverage, base)? as usize;
+    let class_count = read_u16(data, 6)? as usize;
+    let marks = data.get(read_u16(data, 8)? as usize..)?;
+    let bases = data.get(read_u16(data, 10)? as usize..)?;
+
+    let record = 2 + mark_index * 4;
+    let class = read_u16(marks, record)? as usize;
+    let mark_anchor = read_anchor(marks, read_u16(marks, record + 2)? as usize)?;
+    if class >= class_count {
+        return None;
+    }
+
+    let base_anchor = read_anchor(bases, anchor(bases, base_index, class, class_count)?)?;
+    Some((base_anchor.0 - mark_anchor.0, base_anchor.1 - mark_anchor.1))
+}
+
+// Returns the anchor offset in a base array or a mark array of mark-to-mark
+// attachment.
+fn base_anchor(bases: &[u8], index: usize, class: usize, class_count: usize) -> Option<usize> {
+    let offset = read_u16(bases, 2 + (index * class_count + class) * 2)? as usize;
+    Some(offset).filter(|&offset| offset != 0)
+}
+
+// Returns the anchor offset of the last component in a ligature array.
+fn ligature_anchor(
+    ligatures: &[u8],
+    index: usize,
+    class: usize,
+    class_count: usize,
+) -> Option<usize> {
+    let attach = read_u16(ligatures, 2 + index * 2)? as usize;
+    let num_components = read_u16(ligatures, attach)? as usize;
+    let component = num_components.checked_sub(1)?;
+    let offset = read_u16(
+        ligatures,
+        attach + 2 + (component * class_count + class) * 2,
+    )?;
+    Some(attach + offset as usize).filter(|_| offset != 0)
+}
+
+// Returns the coordinates of an anchor. Device and variation adjustments and contour
+// points aren't taken into account.
+fn read_anchor(data: &[u8], offset: usize) -> Option<(f32, f32)> {
+    let x = read_i16(data, offset + 2)?;
+    let y = read_i16(data, offset + 4)?;
+    Some((f32::from(x), f32::from(y)))
+}
@@ -0,0 +1,58 @@
+use crate::{Outline, Transform};
+
+impl Outline {
+    /// Returns the outline as `zeno` path commands.
+    pub fn to_zeno_commands(&self) -> Vec<zeno::Command> {
+        let mut builder = ZenoCommandBuilder(Vec::new());
+        self.emit(&mut builder);
+        builder.0
+    }
+
+    /// Renders the outline with `zeno` with `ts` applied, returning the 8-bit mask and
+    /// its placement.
+    ///
+    /// `zeno` is y-down, so pass e.g. `Transform::from_scale(s, -s)` to flip and scale
+    /// the glyph. Pass a `zeno::Fill` or a `zeno::Stroke` as the style.
+    pub fn render_with_zeno<'a>(
+        &self,
+        style: impl Into<zeno::Style<'a>>,
+        ts: Transform,
+    ) -> (Vec<u8>, zeno::Placement) {
+        let commands = self.to_zeno_commands();
+        zeno::Mask::new(&commands)
+            .style(style)
+            .transform(Some(zeno::Transform::new(
+                ts.a, ts.b, ts.c, ts.d, ts.e, ts.f,
+            )))
+            .render()
+    }
+}
+
+struct ZenoCommandBuilder(Vec<zeno::Command>);
+
+impl ttf_parser::OutlineBuilder for ZenoCommandBuilder {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.0.push(zeno::Command::MoveTo([x, y].into()));
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.0.push(zeno::Command::LineTo([x, y].into()));
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        self.0
+            .push(zeno::Command::QuadTo([x1, y1].into(), [x, y].into()));
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        self.0.push(zeno::Command::CurveTo(
+            [x1, y1].into(),
+            [x2, y2].into(),
+            [x, y].into(),
+        ));
+    }
+
+    fn close(&mut self) {
+        self.0.push(zeno::Command::Close);
+    }
+}
@@ -0,0 +1,557 @@
+use crate::geom::{integrate, Segment, MAX_BISECTIONS};
+use crate::{BBox, Contour, FillRule, Outline, Point};
+
+/// A sample of the curvature along a contour.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CurvatureSample {
+    /// The horizontal position.
+    pub x: f32,
+    /// The vertical position.
+    pub y: f32,
+    /// The horizontal part of the unit normal, pointing to the left of the direction.
+    pub nx: f32,
+    /// The vertical part of the unit normal, pointing to the left of the direction.
+    pub ny: f32,
+    /// The signed curvature, i.e. the inverse of the radius, which is positive when
+    /// the contour turns left in a y-up coordinate system.
+    pub curvature: f32,
+}
+
+/// A horizontal side of an outline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Side {
+    /// The left side.
+    Left,
+    /// The right side.
+    Right,
+}
+
+impl Outline {
+    /// Returns the ink box, i.e. the exact bounding box of the outline.
+    ///
+    /// Unlike `control_box`, which includes the off-curve control points, this solves
+    /// for the extrema of the curves, so the result matches the ink extents.
+    pub fn ink_box(&self) -> BBox {
+        if let Some(bbox) = self.ink_box.get() {
+            return bbox;
+        }
+
+        let mut bbox: Option<BBox> = None;
+        let mut extend = |p: Point| match bbox.as_mut() {
+            Some(bbox) => bbox.extend_by(p.x, p.y),
+            None => {
+                bbox = Some(BBox {
+                    x_min: p.x,
+                    y_min: p.y,
+                    x_max: p.x,
+                    y_max: p.y,
+                })
+            }
+        };
+
+        for c in &self.contours {
+            if let Some(p) = c.points.first() {
+                extend(*p);
+            }
+
+            for segment in c.segments() {
+                extend(segment.end());
+                segment.for_each_extremum(|t| extend(segment.eval(t)));
+            }
+        }
+
+        let bbox = bbox.unwrap_or_default();
+        self.ink_box.set(Some(bbox));
+        bbox
+    }
+
+    /// Returns the points where curves reach an extremum along either axis.
+    ///
+    /// Only interior extrema are listed, i.e. the ones a font editor would add as
+    /// new on-curve points. Extrema at segment end points are already present.
+    pub fn extrema(&self) -> Vec<(f32, f32)> {
+        let mut points = Vec::new();
+        for c in &self.contours {
+            for segment in c.segments() {
+                segment.for_each_extremum(|t| {
+                    let p = segment.eval(t);
+                    points.push((p.x, p.y));
+                });
+            }
+        }
+
+        points
+    }
+
+    /// Returns the points where the direction changes by more than `angle_threshold`
+    /// radians.
+    ///
+    /// The directions are the tangents of the segments meeting at each on-curve
+    /// point, including the start point of closed contours.
+    pub fn corners(&self, angle_threshold: f32) -> Vec<(f32, f32)> {
+        let mut corners = Vec::new();
+        for c in &self.contours {
+            let closing_line = if c.is_closed() {
+                c.closing_line()
+            } else {
+                None
+            };
+            let segments: Vec<Segment> = c
+                .segments()
+                .chain(closing_line)
+                .filter(|segment| segment.start_tangent().is_some())
+                .collect();
+            let n = segments.len();
+            // Open contours have no corner at their ends.
+            let start = if c.is_closed() { 0 } else { 1 };
+            for i in start..n {
+                let incoming = segments[(i + n - 1) % n];
+                let outgoing = segments[i];
+                let (d0, d1) = match (incoming.end_tangent(), outgoing.start_tangent()) {
+                    (Some(d0), Some(d1)) => (d0, d1),
+                    _ => continue,
+                };
+
+                let angle = (d0.x * d1.y - d0.y * d1.x)
+                    .atan2(d0.x * d1.x + d0.y * d1.y)
+                    .abs();
+                if angle > angle_threshold {
+                    let p = outgoing.start();
+                    corners.push((p.x, p.y));
+                }
+            }
+        }
+
+        corners
+    }
+
+    /// Returns the curvature sampled along each contour.
+    ///
+    /// Every segment, including the closing line of closed contours, is sampled at
+    /// `samples_per_segment` evenly spaced parameters from its start to its end, so
+    /// curvature changes at on-curve points show up as two samples at the same
+    /// position. Draw a line from each position along the normal scaled by the
+    /// curvature to get a curvature comb.
+    pub fn curvature_profile(&self, samples_per_segment: usize) -> Vec<Vec<CurvatureSample>> {
+        self.contours
+            .iter()
+            .map(|c| {
+                let closing_line = if c.is_closed() {
+                    c.closing_line()
+                } else {
+                    None
+                };
+                let mut samples = Vec::new();
+                for segment in c.segments().chain(closing_line) {
+                    for i in 0..samples_per_segment {
+                        let t = if samples_per_segment > 1 {
+                            i as f32 / (samples_per_segment - 1) as f32
+                        } else {
+                            0.5
+                        };
+                        let p = segment.eval(t);
+                        let d = segment.derivative(t);
+                        let dd = segment.second_derivative(t);
+                        let len = d.length();
+                        if len == 0.0 {
+                            continue;
+                        }
+
+                        samples.push(CurvatureSample {
+                            x: p.x,
+                            y: p.y,
+                            nx: -d.y / len,
+                            ny: d.x / len,
+                            curvature: (d.x * dd.y - d.y * dd.x) / (len * len * len),
+                        });
+                    }
+                }
+
+                samples
+            })
+            .collect()
+    }
+
+    /// Returns the horizontal ink extent on one side of the outline in bands.
+    ///
+    /// The ink box is split into `num_bands` bands of equal height from the bottom to
+    /// the top. Each band has the smallest x of the ink within it for the left side,
+    /// or the largest for the right side, and `None` when it has no ink. Curves are
+    /// flattened within a hundredth of the band height.
+    pub fn edge_profile(&self, side: Side, num_bands: usize) -> Vec<Option<f32>> {
+        let mut bands = vec![None; num_bands];
+        let bbox = self.ink_box();
+        if num_bands == 0 || self.is_empty() || bbox.height() <= 0.0 {
+            return bands;
+        }
+
+        let band_height = bbox.height() / num_bands as f32;
+        let tolerance = self.flatten_tolerance(band_height);
+        let mut extend = |band: usize, x: f32| {
+            let extent: &mut Option<f32> = &mut bands[band.min(num_bands - 1)];
+            *extent = Some(match (*extent, side) {
+                (None, _) => x,
+                (Some(e), Side::Left) => e.min(x),
+                (Some(e), Side::Right) => e.max(x),
+            });
+        };
+
+        let band_of =
+            |y: f32| (((y - bbox.y_min) / band_height).max(0.0) as usize).min(num_bands - 1);
+        for c in &self.contours {
+            let points = c.flatten_points(tolerance);
+            let n = points.len();
+            for i in 0..n {
+                let (p0, p1) = (points[i], points[(i + 1) % n]);
+                let (lo, hi) = if p0.y <= p1.y { (p0, p1) } else { (p1, p0) };
+                extend(band_of(lo.y), lo.x);
+                extend(band_of(hi.y), hi.x);
+
+                // Where the edge crosses into the next band, it's in both.
+                for band in band_of(lo.y) + 1..=band_of(hi.y) {
+                    let y = bbox.y_min + band as f32 * band_height;
+                    let x = lo.x + (hi.x - lo.x) * (y - lo.y) / (hi.y - lo.y);
+                    extend(band - 1, x);
+                    extend(band, x);
+                }
+            }
+        }
+
+        bands
+    }
+
+    /// Returns the area of the outline.
+    ///
+    /// This is the absolute value of the sum of the signed contour areas, which is the
+    /// filled area as long as holes run in the opposite direction of the outer contours
+    /// and no contours overlap.
+    pub fn area(&self) -> f32 {
+        self.contours
+            .iter()
+            .map(|c| c.signed_area())
+            .sum::<f32>()
+            .abs()
+    }
+
+    /// Returns the area-weighted centroid of the outline.
+    ///
+    /// Holes are subtracted as long as they run in the opposite direction of the outer
+    /// contours. Returns `None` when the outline has no area.
+    pub fn centroid(&self) -> Option<(f32, f32)> {
+        let mut area = 0.0;
+        let mut mx = 0.0;
+        let mut my = 0.0;
+        for c in &self.contours {
+            for segment in c.segments().chain(c.closing_line()) {
+                area += segment.signed_area();
+                // Green's theorem, integrated exactly for polynomial curves.
+                mx += integrate(|t| {
+                    let p = segment.eval(t);
+                    p.x * p.x * segment.derivative(t).y
+                }) * 0.5;
+                my -= integrate(|t| {
+                    let p = segment.eval(t);
+                    p.y * p.y * segment.derivative(t).x
+                }) * 0.5;
+            }
+        }
+
+        if area.abs() <= f32::EPSILON {
+            return None;
+        }
+
+        Some((mx / area, my / area))
+    }
+
+    /// Returns the convex hull of the outline.
+    ///
+    /// Curves are flattened within `tolerance` first. The hull points are returned in
+    /// counter-clockwise order in a y-up coordinate system.
+    pub fn convex_hull(&self, tolerance: f32) -> Vec<(f32, f32)> {
+        let mut points: Vec<Point> = self
+            .contours
+            .iter()
+            .flat_map(|c| c.flatten_points(tolerance))
+            .collect();
+        points.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+        points.dedup();
+        if points.len() < 3 {
+            return points.iter().map(|p| (p.x, p.y)).collect();
+        }
+
+        // Andrew's monotone chain.
+        let cross =
+            |o: Point, a: Point, b: Point| (a.x - o.x) * (b.y - o.y) - (a.y - o.y) * (b.x - o.x);
+        let mut hull: Vec<Point> = Vec::with_capacity(points.len() + 1);
+        // The lower chain is built left to right and the upper one right to left.
+        for &p in &points {
+            while hull.len() >= 2 && cross(hull[hull.len() - 2], hull[hull.len() - 1], p) <= 0.0 {
+                hull.pop();
+            }
+
+            hull.push(p);
+        }
+
+        let lower_len = hull.len() + 1;
+        for &p in points.iter().rev().skip(1) {
+            while hull.len() >= lower_len
+                && cross(hull[hull.len() - 2], hull[hull.len() - 1], p) <= 0.0
+            {
+                hull.pop();
+            }
+
+            hull.push(p);
+        }
+
+        // The last point is the first one again.
+        hull.pop();
+
+        hull.iter().map(|p| (p.x, p.y)).collect()
+    }
+
+    /// Returns the smallest circle enclosing the outline as `(cx, cy, radius)`.
+    ///
+    /// Curves are flattened within `tolerance` first. Returns `None` for an empty
+    /// outline.
+    pub fn bounding_circle(&self, tolerance: f32) -> Option<(f32, f32, f32)> {
+        // The smallest enclosing circle of the hull is the same as the one of all points.
+        let points: Vec<Point> = self
+            .convex_hull(tolerance)
+            .into_iter()
+            .map(|(x, y)| Point::new(x, y))
+            .collect();
+        let mut circle = Circle::new(*points.first()?, 0.0);
+        // Welzl's algorithm in its iterative form.
+        for i in 1..points.len() {
+            if circle.contains(points[i]) {
+                continue;
+            }
+
+            circle = Circle::new(points[i], 0.0);
+            for j in 0..i {
+                if circle.contains(points[j]) {
+                    continue;
+                }
+
+                circle = Circle::from_diameter(points[i], points[j]);
+                for k in 0..j {
+                    if !circle.contains(points[k]) {
+                        circle = Circle::from_triangle(points[i], points[j], points[k]);
+                    }
+                }
+            }
+        }
+
+        Some((circle.center.x, circle.center.y, circle.radius))
+    }
+
+    /// Returns the minimum area rectangle enclosing the outline.
+    ///
+    /// Curves are flattened within `tolerance` first. The corners are returned in
+    /// counter-clockwise order in a y-up coordinate system. Returns `None` for an
+    /// empty outline.
+    pub fn oriented_bbox(&self, tolerance: f32) -> Option<[(f32, f32); 4]> {
+        let hull: Vec<Point> = self
+            .convex_hull(tolerance)
+            .into_iter()
+            .map(|(x, y)| Point::new(x, y))
+            .collect();
+        let first = *hull.first()?;
+
+        // One side of the minimum area rectangle is collinear with a hull edge.
+        let mut best: Option<(f32, [Point; 4])> = None;
+        for (i, &p0) in hull.iter().enumerate() {
+            let p1 = hull[(i + 1) % hull.len()];
+            let len = p0.distance(p1);
+            if len <= f32::EPSILON {
+                continue;
+            }
+
+            let u = (p1 - p0) * (1.0 / len);
+            let v = Point::new(-u.y, u.x);
+            let (mut u_min, mut u_max) = (f32::MAX, f32::MIN);
+            let (mut v_min, mut v_max) = (f32::MAX, f32::MIN);
+            for &p in &hull {
+                let d = p - p0;
+                let pu = d.x * u.x + d.y * u.y;
+                let pv = d.x * v.x + d.y * v.y;
+                u_min = u_min.min(pu);
+                u_max = u_max.max(pu);
+                v_min = v_min.min(pv);
+                v_max = v_max.max(pv);
+            }
+
+            let area = (u_max - u_min) * (v_max - v_min);
+            if matches!(best, Some((best_area, _)) if best_area <= area) {
+                continue;
+            }
+
+            let corner = |pu: f32, pv: f32| p0 + u * pu + v * pv;
+            best = Some((
+                area,
+                [
+                    corner(u_min, v_min),
+                    corner(u_max, v_min),
+                    corner(u_max, v_max),
+                    corner(u_min, v_max),
+                ],
+            ));
+        }
+
+        // A single point has no edges.
+        let corners = best.map_or([first; 4], |(_, corners)| corners);
+        Some([
+            (corners[0].x, corners[0].y),
+            (corners[1].x, corners[1].y),
+            (corners[2].x, corners[2].y),
+            (corners[3].x, corners[3].y),
+        ])
+    }
+
+    /// Returns `true` if the point is inside the outline according to `fill_rule`.
+    ///
+    /// Curves are handled exactly and all contours are treated as closed.
+    pub fn contains(&self, x: f32, y: f32, fill_rule: FillRule) -> bool {
+        let bbox = self.control_box();
+        if x < bbox.x_min || x > bbox.x_max || y < bbox.y_min || y > bbox.y_max {
+            return false;
+        }
+
+        let winding: i32 = self
+            .contours
+            .iter()
+            .map(|c| c.winding_number(Point::new(x, y)))
+            .sum();
+        match fill_rule {
+            FillRule::NonZero => winding != 0,
+            FillRule::EvenOdd => winding % 2 != 0,
+        }
+    }
+
+    /// Returns the total length of all contours.
+    ///
+    /// See `Contour::perimeter` for details.
+    pub fn perimeter(&self, tolerance: f32) -> f32 {
+        self.contours.iter().map(|c| c.perimeter(tolerance)).sum()
+    }
+}
+
+impl Contour {
+    /// Returns the signed area of the contour.
+    ///
+    /// The area is positive for counter-clockwise contours in a y-up coordinate
+    /// system. Curves are accounted for exactly and the contour is treated as closed.
+    pub fn signed_area(&self) -> f32 {
+        self.segments()
+            .chain(self.closing_line())
+            .map(|segment| segment.signed_area())
+            .sum()
+    }
+
+    /// Returns how many times the contour winds around `p` counter-clockwise in a
+    /// y-up coordinate system, counting a horizontal ray to the right of `p`.
+    pub(crate) fn winding_number(&self, p: Point) -> i32 {
+        let mut winding = 0;
+        for segment in self.segments().chain(self.closing_line()) {
+            // Split at the extrema so every piece is monotonic in y.
+            let mut ts = vec![0.0, 1.0];
+            segment.for_each_extremum(|t| ts.push(t));
+            ts.sort_by(|a, b| a.total_cmp(b));
+            for w in ts.windows(2) {
+                let (t0, t1) = (w[0], w[1]);
+                let y0 = segment.eval(t0).y;
+                let y1 = segment.eval(t1).y;
+                // Half open intervals so shared end points are only counted once.
+                let dir = if y0 <= p.y && p.y < y1 {
+                    1
+                } else if y1 <= p.y && p.y < y0 {
+                    -1
+                } else {
+                    continue;
+                };
+
+                let (mut lo, mut hi) = if y0 < y1 { (t0, t1) } else { (t1, t0) };
+                for _ in 0..MAX_BISECTIONS {
+                    let mid = (lo + hi) * 0.5;
+                    if segment.eval(mid).y < p.y {
+                        lo = mid;
+                    } else {
+                        hi = mid;
+                    }
+                }
+
+                if segment.eval((lo + hi) * 0.5).x > p.x {
+                    winding += dir;
+                }
+            }
+        }
+
+        winding
+    }
+
+    /// Returns the length of the contour.
+    ///
+    /// Curves are measured by adaptive subdivision until within `tolerance`. The
+    /// closing line is included when the contour is closed.
+    pub fn perimeter(&self, tolerance: f32) -> f32 {
+        let closing_line = if self.is_closed() {
+            self.closing_line()
+        } else {
+            None
+        };
+        self.segments()
+            .chain(closing_line)
+            .map(|segment| segment.arc_length(tolerance))
+            .sum()
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Circle {
+    center: Point,
+    radius: f32,
+}
+
+impl Circle {
+    #[inline]
+    fn new(center: Point, radius: f32) -> Self {
+        Self { center, radius }
+    }
+
+    fn from_diameter(a: Point, b: Point) -> Self {
+        Self::new(a.lerp(b, 0.5), a.distance(b) * 0.5)
+    }
+
+    fn from_triangle(a: Point, b: Point, c: Point) -> Self {
+        let ab = b - a;
+        let ac = c - a;
+        let d = 2.0 * (ab.x * ac.y - ab.y * ac.x);
+        if d.abs() <= f32::EPSILON {
+            // Collinear points are enclosed by the circle through the two farthest ones.
+            return [
+                Self::from_diameter(a, b),
+                Self::from_diameter(a, c),
+                Self::from_diameter(b, c),
+            ]
+            .iter()
+            .copied()
+            .fold(
+                Self::new(a, 0.0),
+                |m, c| if c.radius > m.radius { c } else { m },
+            );
+        }
+
+        let ab2 = ab.x * ab.x + ab.y * ab.y;
+        let ac2 = ac.x * ac.x + ac.y * ac.y;
+        let center = a + Point::new((ac.y * ab2 - ab.y * ac2) / d, (ab.x * ac2 - ac.x * ab2) / d);
+        Self::new(center, center.distance(a))
+    }
+
+    #[inline]
+    fn contains(&self, p: Point) -> bool {
+        // Allow for some rounding errors, relative to the size of the circle.
+        self.center.distance(p) <= self.radius * (1.0 + 1e-5) + 1e-5
+    }
+}
@@ -0,0 +1,611 @@
+use crate::{Outline, Point};
+
+/// The triangles of a filled outline, suitable for direct GPU upload.
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Mesh {
+    /// Interleaved `x, y` vertex coordinates.
+    pub vertices: Vec<f32>,
+    /// Three vertex indices per triangle, counter-clockwise in a y-up coordinate
+    /// system.
+    pub indices: Vec<u32>,
+}
+
+impl Outline {
+    /// Returns the outline flattened within `tolerance` and triangulated by ear
+    /// clipping.
+    ///
+    /// Holes are the contours nested directly within others, see `contour_tree`, that
+    /// wind the other way, and they're bridged into the contours around them. Nested
+    /// contours that wind the same way, like overlapping components, are triangulated
+    /// on their own, so their triangles overlap. Use `remove_overlaps` first for
+    /// meshes without overlaps.
+    pub fn triangulate(&self, tolerance: f32) -> Mesh {
+        let mut mesh = Mesh::default();
+        let mut bases = Vec::with_capacity(self.contours.len());
+        let mut polygons = Vec::with_capacity(self.contours.len());
+        for c in &self.contours {
+            let mut points = c.flatten_points(tolerance);
+            points.dedup();
+            if points.len() > 1 && points.first() == points.last() {
+                points.pop();
+            }
+
+            bases.push(mesh.vertices.len() / 2);
+            mesh.vertices.extend(points.iter().flat_map(|p| [p.x, p.y]));
+            polygons.push(points);
+        }
+
+        let mut earcut = Earcut {
+            nodes: Vec::new(),
+            indices: &mut mesh.indices,
+        };
+        let mut stack = self.contour_tree();
+        while let Some(outer) = stack.pop() {
+            earcut.nodes.clear();
+            let list = earcut.linked_list(&polygons[outer.index], bases[outer.index], true);
+            let mut holes: Vec<usize> = Vec::new();
+            let winding = self.contours[outer.index].signed_area() > 0.0;
+            for child in outer.children {
+                if (self.contours[child.index].signed_area() > 0.0) == winding {
+                    stack.push(child);
+                    continue;
+                }
+
+                let hole = earcut.linked_list(&polygons[child.index], bases[child.index], false);
+                holes.extend(hole.map(|hole| earcut.leftmost(hole)));
+                stack.extend(child.children);
+            }
+
+            let mut list = match list {
+                Some(list) if earcut.nodes[list].next != earcut.nodes[list].prev => list,
+                _ => continue,
+            };
+
+            holes.sort_by(|&a, &b| earcut.nodes[a].x.total_cmp(&earcut.nodes[b].x));
+            for hole in holes {
+                list = earcut.eliminate_hole(hole, list);
+            }
+
+            earcut.earcut_linked(Some(list), 0);
+        }
+
+        mesh
+    }
+}
+
+// A vertex in a circular doubly linked list of polygon vertices.
+struct Node {
+    i: u32,
+    x: f32,
+    y: f32,
+    prev: usize,
+    next: usize,
+}
+
+// The ear clipping algorithm of the earcut library by Mapbox, without the z-order
+// curve that only speeds up large polygons.
+struct Earcut<'a> {
+    nodes: Vec<Node>,
+    indices: &'a mut Vec<u32>,
+}
+
+impl Earcut<'_> {
+    // Returns the last node of a new list of `points`, ordered so the signed area
+    // has the given sign, or `None` for less than three points.
+    fn linked_list(&mut self, points: &[Point], base: usize, positive: bool) -> Option<usize> {
+        if points.len() < 3 {
+            return None;
+        }
+
+        let area: f32 = (0..points.len())
+            .map(|i| {
+                let p = points[i];
+                let q = points[(i + points.len() - 1) % points.len()];
+                (q.x - p.x) * (p.y + q.y)
+            })
+            .sum();
+
+        let mut last = None;
+        let mut insert = |earcut: &mut Self, i: usize| {
+            let p = points[i];
+            last = Some(earcut.insert((base + i) as u32, p.x, p.y, last));
+        };
+
+        if positive == (area > 0.0) {
+            (0..points.len()).for_each(|i| insert(self, i));
+        } else {
+            (0..points.len()).rev().for_each(|i| insert(self, i));
+        }
+
+        let last = last?;
+        if self.equals(last, self.nodes[last].next) {
+            self.remove(last);
+            return Some(self.nodes[last].next);
+        }
+
+        Some(last)
+    }
+
+    fn insert(&mut self, i: u32, x: f32, y: f32, last: Option<usize>) -> usize {
+        let p = self.nodes.len();
+        let (prev, next) = match last {
+            Some(last) => (last, self.nodes[last].next),
+            None => (p, p),
+        };
+
+        self.nodes.push(Node {
+            i,
+            x,
+            y,
+            prev,
+            next,
+        });
+        if let Some(last) = last {
+            self.nodes[next].prev = p;
+            self.nodes[last].next = p;
+        }
+
+        p
+    }
+
+    fn remove(&mut self, p: usize) {
+        let Node { prev, next, .. } = self.nodes[p];
+        self.nodes[next].prev = prev;
+        self.nodes[prev].next = next;
+    }
+
+    fn earcut_linked(&mut self, ear: Option<usize>, pass: u8) {
+        let mut ear = match ear {
+            Some(ear) => ear,
+            None => return,
+        };
+
+        let mut stop = ear;
+        while self.nodes[ear].prev != self.nodes[ear].next {
+            let prev = self.nodes[ear].prev;
+            let next = self.nodes[ear].next;
+            if self.is_ear(ear) {
+                self.indices
+                    .extend([self.nodes[prev].i, self.nodes[ear].i, self.nodes[next].i]);
+                self.remove(ear);
+                ear = self.nodes[next].next;
+                stop = ear;
+                continue;
+            }
+
+            ear = next;
+            if ear == stop {
+                // Try again after removing redundant points, then after clipping
+                // self-intersections and last by splitting the polygon in two.
+                match pass {
+                    0 => {
+                        let ear = self.filter_points(ear, None);
+                        self.earcut_linked(ear, 1);
+                    }
+                    1 => {
+                        let ear = self
+                            .filter_points(ear, None)
+                            .map(|ear| self.cure_local_intersections(ear));
+                        self.earcut_linked(ear.flatten(), 2);
+                    }
+                    _ => self.split_earcut(ear),
+                }
+
+                break;
+            }
+        }
+    }
+
+    fn is_ear(&self, ear: usize) -> bool {
+        let (a, b, c) = (self.nodes[ear].prev, ear, self.nodes[ear].next);
+        if self.area(a, b, c) >= 0.0 {
+            // Reflex.
+            return false;
+        }
+
+        let (a, b, c) = (&self.nodes[a], &self.nodes[b], &self.nodes[c]);
+        let x_min = a.x.min(b.x).min(c.x);
+        let y_min = a.y.min(b.y).min(c.y);
+        let x_max = a.x.max(b.x).max(c.x);
+        let y_max = a.y.max(b.y).max(c.y);
+        let mut p = c.next;
+        while p != self.nodes[ear].prev {
+            let n = &self.nodes[p];
+            if n.x >= x_min
+                && n.x <= x_max
+                && n.y >= y_min
+                && n.y <= y_max
+                && point_in_triangle(a.x, a.y, b.x, b.y, c.x, c.y, n.x, n.y)
+                && self.area(n.prev, p, n.next) >= 0.0
+            {
+                return false;
+            }
+
+            p = n.next;
+        }
+
+        true
+    }
+
+    // Clips the triangles of local self-intersections, like the ones at loops.
+    fn cure_local_intersections(&mut self, mut start: usize) -> Option<usize> {
+        let mut p = start;
+        loop {
+            let a = self.nodes[p].prev;
+            let next = self.nodes[p].next;
+            let b = self.nodes[next].next;
+            if !self.equals(a, b)
+                && self.intersects(a, p, next, b)
+                && self.locally_inside(a, b)
+                && self.locally_inside(b, a)
+            {
+                self.indices
+                    .extend([self.nodes[a].i, self.nodes[p].i, self.nodes[b].i]);
+                self.remove(p);
+                self.remove(next);
+                p = b;
+                start = b;
+            }
+
+            p = self.nodes[p].next;
+            if p == start {
+                break;
+            }
+        }
+
+        self.filter_points(p, None)
+    }
+
+    // Splits the polygon along a valid diagonal and triangulates both halves.
+    fn split_earcut(&mut self, start: usize) {
+        let mut a = start;
+        loop {
+            let mut b = self.nodes[self.nodes[a].next].next;
+            while b != self.nodes[a].prev {
+                if self.nodes[a].i != self.nodes[b].i && self.is_valid_diagonal(a, b) {
+                    let c = self.split_polygon(a, b);
+                    let a = self.filter_points(a, Some(self.nodes[a].next));
+                    let c = self.filter_points(c, Some(self.nodes[c].next));
+                    self.earcut_linked(a, 0);
+                    self.earcut_linked(c, 0);
+                    return;
+                }
+
+                b = self.nodes[b].next;
+            }
+
+            a = self.nodes[a].next;
+            if a == start {
+                break;
+            }
+        }
+    }
+
+    // Connects a hole to the outer polygon and returns the new outer polygon.
+    fn eliminate_hole(&mut self, hole: usize, outer: usize) -> usize {
+        let bridge = match self.find_hole_bridge(hole, outer) {
+            Some(bridge) => bridge,
+            None => return outer,
+        };
+
+        let reverse = self.split_polygon(bridge, hole);
+        self.filter_points(reverse, Some(self.nodes[reverse].next));
+        self.filter_points(bridge, Some(self.nodes[bridge].next))
+            .unwrap_or(bridge)
+    }
+
+    // Returns the outer node the leftmost node of a hole can be connected to with a
+    // segment that doesn't cross the polygon, following David Eberly.
+    fn find_hole_bridge(&self, hole: usize, outer: usize) -> Option<usize> {
+        let (hx, hy) = (self.nodes[hole].x, self.nodes[hole].y);
+        let mut qx = f32::NEG_INFINITY;
+        let mut m = None;
+        // Find the segment left of the hole that a ray to the left hits first.
+        let mut p = outer;
+        loop {
+            let (n, next) = (&self.nodes[p], &self.nodes[self.nodes[p].next]);
+            if hy <= n.y && hy >= next.y && next.y != n.y {
+                let x = n.x + (hy - n.y) * (next.x - n.x) / (next.y - n.y);
+                if x <= hx && x > qx {
+                    qx = x;
+                    m = Some(if n.x < next.x { p } else { n.next });
+                    if x == hx {
+                        return m;
+                    }
+                }
+            }
+
+            p = n.next;
+            if p == outer {
+                break;
+            }
+        }
+
+        // Look for points inside the triangle of the hole point, the segment
+        // intersection and its end point, and pick the one with the smallest angle
+        // to the ray, since it's visible.
+        let mut m = m?;
+        let stop = m;
+        let (mx, my) = (self.nodes[m].x, self.nodes[m].y);
+        let mut tan_min = f32::INFINITY;
+        p = m;
+        loop {
+            let n = &self.nodes[p];
+            let (ax, cx) = if hy < my { (hx, qx) } else { (qx, hx) };
+            if hx >= n.x
+                && n.x >= mx
+                && hx != n.x
+                && point_in_triangle(ax, hy, mx, my, cx, hy, n.x, n.y)
+            {
+                let tan = (hy - n.y).abs() / (hx - n.x);
+                if self.locally_inside(p, hole)
+                    && (tan < tan_min
+                        || (tan == tan_min
+                            && (n.x > self.nodes[m].x
+                                || (n.x == self.nodes[m].x && self.sector_contains_sector(m, p)))))
+                {
+                    m = p;
+                    tan_min = tan;
+                }
+            }
+
+            p = n.next;
+            if p == stop {
+                break;
+            }
+        }
+
+        Some(m)
+    }
+
+    // Returns `true` if the sector of `m` contains the sector of `p` in the
+    // collinear case.
+    fn sector_contains_sector(&self, m: usize, p: usize) -> bool {
+        let (m_prev, m_next) = (self.nodes[m].prev, self.nodes[m].next);
+        let (p_prev, p_next) = (self.nodes[p].prev, self.nodes[p].next);
+        self.area(m_prev, m, p_prev) < 0.0 && self.area(p_next, m, m_next) < 0.0
+    }
+
+    fn leftmost(&self, start: usize) -> usize {
+        let mut p = start;
+        let mut leftmost = start;
+        loop {
+            let (n, l) = (&self.nodes[p], &self.nodes[leftmost]);
+            if n.x < l.x || (n.x == l.x && n.y < l.y) {
+                leftmost = p;
+            }
+
+            p = n.next;
+            if p == start {
+                break;
+            }
+        }
+
+        leftmost
+    }
+
+    // Removes duplicate and collinear points, returning the last node or `None`
+    // when the polygon collapses to a single point.
+    fn filter_points(&mut self, start: usize, end: Option<usize>) -> Option<usize> {
+        let mut end = end.unwrap_or(start);
+        let mut p = start;
+        loop {
+            let Node { prev, next, .. } = self.nodes[p];
+            if self.equals(p, next) || self.area(prev, p, next) == 0.0 {
+                self.remove(p);
+                p = prev;
+                end = prev;
+                if p == self.nodes[p].next {
+                    return None;
+                }
+
+                continue;
+            }
+
+            p = next;
+            if p == end {
+                return Some(end);
+            }
+        }
+    }
+
+    fn is_valid_diagonal(&self, a: usize, b: usize) -> bool {
+        let (na, nb) = (&self.nodes[a], &self.nodes[b]);
+        self.nodes[na.next].i != nb.i
+            && self.nodes[na.prev].i != nb.i
+            && !self.intersects_polygon(a, b)
+            && ((self.locally_inside(a, b)
+                && self.locally_inside(b, a)
+                && self.middle_inside(a, b)
+                && (self.area(na.prev, a, nb.prev) != 0.0 || self.area(a, nb.prev, b) != 0.0))
+                || (self.equals(a, b)
+                    && self.area(na.prev, a, na.next) > 0.0
+                    && self.area(nb.prev, b, nb.next) > 0.0))
+    }
+
+    fn intersects_polygon(&self, a: usize, b: usize) -> bool {
+        let (ai, bi) = (self.nodes[a].i, self.nodes[b].i);
+        let mut p = a;
+        loop {
+            let n = &self.nodes[p];
+            let next_i = self.nodes[n.next].i;
+            if n.i != ai
+                && next_i != ai
+                && n.i != bi
+                && next_i != bi
+                && self.intersects(p, n.next, a, b)
+            {
+                return true;
+            }
+
+            p = n.next;
+            if p == a {
+                return false;
+            }
+        }
+    }
+
+    fn locally_inside(&self, a: usize, b: usize) -> bool {
+        let (prev, next) = (self.nodes[a].prev, self.nodes[a].next);
+        if self.area(prev, a, next) < 0.0 {
+            self.area(a, b, next) >= 0.0 && self.area(a, prev, b) >= 0.0
+        } else {
+            self.area(a, b, prev) < 0.0 || self.area(a, next, b) < 0.0
+        }
+    }
+
+    fn middle_inside(&self, a: usize, b: usize) -> bool {
+        let px = (self.nodes[a].x + self.nodes[b].x) * 0.5;
+        let py = (self.nodes[a].y + self.nodes[b].y) * 0.5;
+        let mut inside = false;
+        let mut p = a;
+        loop {
+            let (n, next) = (&self.nodes[p], &self.nodes[self.nodes[p].next]);
+            if (n.y > py) != (next.y > py)
+                && next.y != n.y
+                && px < (next.x - n.x) * (py - n.y) / (next.y - n.y) + n.x
+            {
+                inside = !inside;
+            }
+
+            p = n.next;
+            if p == a {
+                return inside;
+            }
+        }
+    }
+
+    // Links `a` to `b` with a diagonal, which splits the polygon in two, and returns
+    // the copy of `b` in the second polygon.
+    fn split_polygon(&mut self, a: usize, b: usize) -> usize {
+        let a2 = self.nodes.len();
+        let b2 = a2 + 1;
+        let an = self.nodes[a].next;
+        let bp = self.nodes[b].prev;
+        let (ai, ax, ay) = (self.nodes[a].i, self.nodes[a].x, self.nodes[a].y);
+        let (bi, bx, by) = (self.nodes[b].i, self.nodes[b].x, self.nodes[b].y);
+        self.nodes.push(Node {
+            i: ai,
+            x: ax,
+            y: ay,
+            prev: b2,
+            next: an,
+        });
+        self.nodes.push(Node {
+            i: bi,
+            x: bx,
+            y: by,
+            prev: bp,
+            next: a2,
+        });
+        self.nodes[a].next = b;
+        self.nodes[b].prev = a;
+        self.nodes[an].prev = a2;
+        self.nodes[bp].next = b2;
+        b2
+    }
+
+    fn intersects(&self, p1: usize, q1: usize, p2: usize, q2: usize) -> bool {
+        let o1 = sign(self.area(p1, q1, p2));
+        let o2 = sign(self.area(p1, q1, q2));
+        let o3 = sign(self.area(p2, q2, p1));
+        let o4 = sign(self.area(p2, q2, q1));
+        (o1 != o2 && o3 != o4)
+            || (o1 == 0 && self.on_segment(p1, p2, q1))
+            || (o2 == 0 && self.on_segment(p1, q2, q1))
+            || (o3 == 0 && self.on_segment(p2, p1, q2))
+            || (o4 == 0 && self.on_segment(p2, q1, q2))
+    }
+
+    // Returns `true` if `q` is within the bounds of the collinear segment `p`-`r`.
+    fn on_segment(&self, p: usize, q: usize, r: usize) -> bool {
+        let (p, q, r) = (&self.nodes[p], &self.nodes[q], &self.nodes[r]);
+        q.x <= p.x.max(r.x) && q.x >= p.x.min(r.x) && q.y <= p.y.max(r.y) && q.y >= p.y.min(r.y)
+    }
+
+    #[inline]
+    fn area(&self, p: usize, q: usize, r: usize) -> f32 {
+        let (p, q, r) = (&self.nodes[p], &self.nodes[q], &self.nodes[r]);
+        (q.y - p.y) * (r.x - q.x) - (q.x - p.x) * (r.y - q.y)
+    }
+
+    #[inline]
+    fn equals(&self, a: usize, b: usize) -> bool {
+        self.nodes[a].x == self.nodes[b].x && self.nodes[a].y == self.nodes[b].y
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+#[inline]
+fn point_in_triangle(
+    ax: f32,
+    ay: f32,
+    bx: f32,
+    by: f32,
+    cx: f32,
+    cy: f32,
+    px: f32,
+    py: f32,
+) -> bool {
+    (cx - px) * (ay - py) >= (ax - px) * (cy - py)
+        && (ax - px) * (by - py) >= (bx - px) * (ay - py)
+        && (bx - px) * (cy - py) >= (cx - px) * (by - py)
+}
+
+#[inline]
+fn sign(n: f32) -> i8 {
+    if n > 0.0 {
+        1
+    } else if n < 0.0 {
+        -1
+    } else {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Outline;
+
+    fn square(outline: &mut Outline, x: f32, y: f32, size: f32, clockwise: bool) {
+        outline.move_to(x, y);
+        if clockwise {
+            outline.line_to(x, y + size);
+            outline.line_to(x + size, y + size);
+            outline.line_to(x + size, y);
+        } else {
+            outline.line_to(x + size, y);
+            outline.line_to(x + size, y + size);
+            outline.line_to(x, y + size);
+        }
+        outline.close();
+    }
+
+    #[test]
+    fn square_with_hole() {
+        for &clockwise in &[false, true] {
+            let mut outline = Outline::default();
+            square(&mut outline, 0.0, 0.0, 10.0, clockwise);
+            square(&mut outline, 3.0, 3.0, 4.0, !clockwise);
+            let mesh = outline.triangulate(0.1);
+            assert_eq!(mesh.vertices.len(), 16);
+            // Eight vertices with one hole make eight triangles.
+            assert_eq!(mesh.indices.len(), 24);
+
+            let point = |i: u32| {
+                let i = i as usize * 2;
+                (mesh.vertices[i], mesh.vertices[i + 1])
+            };
+            let mut area = 0.0;
+            for triangle in mesh.indices.chunks(3) {
+                let (a, b, c) = (point(triangle[0]), point(triangle[1]), point(triangle[2]));
+                let doubled = (b.0 - a.0) * (c.1 - a.1) - (b.1 - a.1) * (c.0 - a.0);
+                assert!(doubled > 0.0, "{:?} isn't counter-clockwise", triangle);
+                area += doubled * 0.5;
+            }
+
+            assert!((area - 84.0).abs() < 1e-3, "area {}", area);
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use crate::Outline;
+
+/// The metrics of a glyph at the current variation of a face.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GlyphMetrics {
+    /// The horizontal advance.
+    pub advance_width: f32,
+    /// The vertical advance, if the face has vertical metrics.
+    pub advance_height: Option<f32>,
+    /// The distance from the origin to the left of the control box.
+    pub left_side_bearing: f32,
+    /// The distance from the right of the control box to the advance width.
+    pub right_side_bearing: f32,
+    /// The distance from the top of the control box to the vertical origin, if the
+    /// face has vertical metrics.
+    pub top_side_bearing: Option<f32>,
+    /// The height of the vertical origin, from the `VORG` table or the vertical
+    /// metrics, if the face has either.
+    pub vertical_origin: Option<f32>,
+}
+
+impl Outline {
+    /// Returns a new outline along with its metrics or `None` when the glyph has no
+    /// outline or on error.
+    ///
+    /// For variable fonts both follow the variations set on the face. The advances
+    /// include the deltas of the `HVAR` and `VVAR` tables, and the side bearings are
+    /// measured on the varied outline. Fonts that only vary their advances with the
+    /// phantom points in `gvar` keep their default advances, since `ttf-parser`
+    /// doesn't expose those points.
+    pub fn new_with_metrics(
+        face: &ttf_parser::Face,
+        glyph_id: ttf_parser::GlyphId,
+    ) -> Option<(Self, GlyphMetrics)> {
+        let outline = Self::new(face, glyph_id)?;
+        let advance_width = face.glyph_hor_advance(glyph_id)? as f32;
+        let bbox = outline.control_box();
+        let top_side_bearing = face.glyph_ver_side_bearing(glyph_id).map(f32::from);
+        let metrics = GlyphMetrics {
+            advance_width,
+            advance_height: face.glyph_ver_advance(glyph_id).map(f32::from),
+            left_side_bearing: bbox.x_min,
+            right_side_bearing: advance_width - bbox.x_max,
+            top_side_bearing,
+            vertical_origin: face
+                .glyph_y_origin(glyph_id)
+                .map(f32::from)
+                .or_else(|| top_side_bearing.map(|tsb| bbox.y_max + tsb)),
+        };
+
+        Some((outline, metrics))
+    }
+}
+
+// Returns the vertical advance and origin of a glyph, which fall back to the distance
+// between the ascender and the descender and to the ascender.
+pub(crate) fn vertical_metrics(
+    face: &ttf_parser::Face,
+    glyph_id: ttf_parser::GlyphId,
+    outline: Option<&Outline>,
+) -> (f32, f32) {
+    let ascender = f32::from(face.ascender());
+    let advance = face
+        .glyph_ver_advance(glyph_id)
+        .map_or(ascender - f32::from(face.descender()), f32::from);
+    let origin = face.glyph_y_origin(glyph_id).map(f32::from).or_else(|| {
+        let tsb = face.glyph_ver_side_bearing(glyph_id)?;
+        let y_max = outline.map_or(0.0, |outline| outline.control_box().y_max);
+        Some(y_max + f32::from(tsb))
+    });
+
+    (advance, origin.unwrap_or(ascender))
+}
@@ -0,0 +1,341 @@
+use crate::geom::Segment;
+use crate::{FillRule, Outline, Point, Transform};
+
+const RED: u8 = 1;
+const GREEN: u8 = 2;
+const BLUE: u8 = 4;
+const CYAN: u8 = GREEN | BLUE;
+const MAGENTA: u8 = RED | BLUE;
+const YELLOW: u8 = RED | GREEN;
+const WHITE: u8 = RED | GREEN | BLUE;
+
+// Joins where the direction turns by more than about 8.6 degrees are corners, like
+// the default angle threshold of 3 radians of msdfgen.
+const CORNER_SIN: f32 = 0.15;
+
+// The number of samples used to find the closest point of a curve before refining it.
+const CURVE_SAMPLES: usize = 8;
+
+const NEWTON_STEPS: usize = 4;
+
+/// A multi-channel signed distance field of an outline.
+///
+/// The outline is the edge where the median of the three channels crosses 128.
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MsdfRaster {
+    /// The width in pixels.
+    pub width: u32,
+    /// The height in pixels.
+    pub height: u32,
+    /// The red, green and blue distances of each pixel, row by row from the top,
+    /// above 128 inside the outline.
+    pub pixels: Vec<u8>,
+    /// The position of the left edge, in pixels from the origin.
+    pub left: i32,
+    /// The position of the top edge, in pixels up from the origin.
+    pub top: i32,
+}
+
+impl Outline {
+    /// Returns the multi-channel signed distance field of the outline with `ts`
+    /// applied, see `rasterize`.
+    ///
+    /// The channels span distances from `-range / 2` to `range / 2` pixels, and the
+    /// raster covers the transformed control box padded by half the range. Contours
+    /// are split at their corners and the edges between corners are colored so that
+    /// two channels meet at each corner, which keeps the corners sharp when the
+    /// median of the channels is sampled. Pixels are corrected to be inside according
+    /// to the non-zero fill rule.
+    pub fn rasterize_msdf(&self, ts: Transform, range: f32) -> MsdfRaster {
+        let mut outline = self.clone();
+        outline.transform(ts);
+        let edges = colored_edges(&outline);
+        if edges.is_empty() {
+            return MsdfRaster::default();
+        }
+
+        let bbox = outline.control_box();
+        let padding = range * 0.5;
+        let left = (bbox.x_min - padding).floor() as i32;
+        let top = (bbox.y_max + padding).ceil() as i32;
+        let width = ((bbox.x_max + padding).ceil() as i32 - left).max(1) as u32;
+        let height = (top - (bbox.y_min - padding).floor() as i32).max(1) as u32;
+        // Distances are positive on the left of counter-clockwise contours.
+        let orientation = outline
+            .contours
+            .iter()
+            .map(|c| c.signed_area())
+            .sum::<f32>()
+            .signum();
+
+        let mut pixels = Vec::with_capacity(width as usize * height as usize * 3);
+        for i in 0..height {
+            for j in 0..width {
+                let p = Point::new(left as f32 + j as f32 + 0.5, top as f32 - i as f32 - 0.5);
+                let mut channels = [RED, GREEN, BLUE].map(|channel| {
+                    edges
+                        .iter()
+                        .filter(|(_, color)| color & channel != 0)
+                        .map(|(segment, _)| EdgeDistance::new(segment, p))
+                        .min_by(|a, b| a.cmp(b))
+                        .map_or(-range, |d| d.pseudo * orientation)
+                });
+
+                let inside = outline.contains(p.x, p.y, FillRule::NonZero);
+                if (median(channels) > 0.0) != inside {
+                    channels = channels.map(|d| -d);
+                }
+
+                for d in channels {
+                    let value = (d / range + 0.5).clamp(0.0, 1.0);
+                    pixels.push((value * 255.0 + 0.5) as u8);
+                }
+            }
+        }
+
+        MsdfRaster {
+            width,
+            height,
+            pixels,
+            left,
+            top,
+        }
+    }
+}
+
+// Returns the segments of all contours with their colors.
+fn colored_edges(outline: &Outline) -> Vec<(Segment, u8)> {
+    let mut edges = Vec::new();
+    for c in &outline.contours {
+        let mut segments: Vec<Segment> = c
+            .segments()
+            .chain(c.closing_line())
+            .filter(|s| s.start_tangent().is_some())
+            .collect();
+        if segments.is_empty() {
+            continue;
+        }
+
+        // The indices of the segments that start at a corner.
+        let corners: Vec<usize> = (0..segments.len())
+            .filter(|&i| {
+                let prev = &segments[(i + segments.len() - 1) % segments.len()];
+                is_corner(prev.end_tangent(), segments[i].start_tangent())
+            })
+            .collect();
+
+        match corners.len() {
+            0 => edges.extend(segments.into_iter().map(|s| (s, WHITE))),
+            1 => {
+                // A teardrop: the contour is split into three parts, which needs at
+                // least three segments.
+                segments.rotate_left(corners[0]);
+                while segments.len() < 3 {
+                    let (a, b) = segments.remove(0).split(0.5);
+                    segments.push(a);
+                    segments.push(b);
+                }
+
+                let n = segments.len();
+                let colors = [MAGENTA, WHITE, YELLOW];
+                edges.extend(
+                    segments
+                        .into_iter()
+                        .enumerate()
+                        .map(|(i, s)| (s, colors[i * 3 / n])),
+                );
+            }
+            _ => {
+                segments.rotate_left(corners[0]);
+                let starts: Vec<usize> = corners.iter().map(|i| i - corners[0]).collect();
+                let mut color = CYAN;
+                for (i, s) in segments.into_iter().enumerate() {
+                    if i > 0 && starts.contains(&i) {
+                        color = next_color(color);
+                        // The last part meets the first one at the first corner.
+                        if Some(&i) == starts.last() && color == CYAN {
+                            color = next_color(color);
+                        }
+                    }
+
+                    edges.push((s, color));
+                }
+            }
+        }
+    }
+
+    edges
+}
+
+fn is_corner(end: Option<Point>, start: Option<Point>) -> bool {
+    let (a, b) = match (end, start) {
+        (Some(a), Some(b)) => (a * (1.0 / a.length()), b * (1.0 / b.length())),
+        _ => return false,
+    };
+
+    dot(a, b) <= 0.0 || cross(a, b).abs() > CORNER_SIN
+}
+
+#[inline]
+fn next_color(color: u8) -> u8 {
+    match color {
+        CYAN => MAGENTA,
+        MAGENTA => YELLOW,
+        _ => CYAN,
+    }
+}
+
+// The distance from a point to a segment.
+struct EdgeDistance {
+    // The unsigned distance to the closest point.
+    distance: f32,
+    // How much the direction to the closest point deviates from the normal of the
+    // segment, which tells apart segments at the same distance at a shared end point.
+    deviation: f32,
+    // The signed distance to the segment extended along its tangents at the end
+    // points.
+    pseudo: f32,
+}
+
+impl EdgeDistance {
+    fn new(segment: &Segment, p: Point) -> Self {
+        let t = closest_t(segment, p);
+        let c = segment.eval(t);
+        let v = p - c;
+        let distance = v.length();
+        let tangent = match segment.derivative(t) {
+            d if d.length() > 0.0 => d,
+            _ if t < 0.5 => segment.start_tangent().unwrap_or(v),
+            _ => segment.end_tangent().unwrap_or(v),
+        };
+        let tangent = tangent * (1.0 / tangent.length());
+        let side = cross(tangent, v);
+        let deviation = if distance > 0.0 {
+            dot(tangent, v).abs() / distance
+        } else {
+            0.0
+        };
+
+        // Beyond the end points the perpendicular distance to the tangent is used.
+        let beyond = (t <= 0.0 && dot(v, tangent) < 0.0) || (t >= 1.0 && dot(v, tangent) > 0.0);
+        let pseudo = if beyond && side.abs() <= distance {
+            side
+        } else {
+            distance.copysign(side)
+        };
+
+        EdgeDistance {
+            distance,
+            deviation,
+            pseudo,
+        }
+    }
+
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.distance
+            .total_cmp(&other.distance)
+            .then(self.deviation.total_cmp(&other.deviation))
+    }
+}
+
+// Returns the parameter of the point of the segment closest to `p`.
+fn closest_t(segment: &Segment, p: Point) -> f32 {
+    if let Segment::Line(p0, p1) = *segment {
+        let d = p1 - p0;
+        return (dot(p - p0, d) / dot(d, d)).clamp(0.0, 1.0);
+    }
+
+    let distance = |t: f32| (segment.eval(t) - p).length();
+    let mut best = (0..=CURVE_SAMPLES)
+        .map(|i| i as f32 / CURVE_SAMPLES as f32)
+        .min_by(|&a, &b| distance(a).total_cmp(&distance(b)))
+        .unwrap_or(0.0);
+
+    for _ in 0..NEWTON_STEPS {
+        let v = segment.eval(best) - p;
+        let d1 = segment.derivative(best);
+        let d2 = segment.second_derivative(best);
+        let denominator = dot(d1, d1) + dot(v, d2);
+        if denominator.abs() <= f32::EPSILON {
+            break;
+        }
+
+        let t = (best - dot(v, d1) / denominator).clamp(0.0, 1.0);
+        if distance(t) >= distance(best) {
+            break;
+        }
+
+        best = t;
+    }
+
+    best
+}
+
+#[inline]
+fn median(c: [f32; 3]) -> f32 {
+    c[0].min(c[1]).max(c[0].max(c[1]).min(c[2]))
+}
+
+#[inline]
+fn dot(a: Point, b: Point) -> f32 {
+    a.x * b.x + a.y * b.y
+}
+
+#[inline]
+fn cross(a: Point, b: Point) -> f32 {
+    a.x * b.y - a.y * b.x
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{FillRule, Outline, Transform};
+
+    #[test]
+    fn median_matches_fill() {
+        // A rounded triangle with a square hole.
+        let mut outline = Outline::default();
+        outline.move_to(0.0, 0.0);
+        outline.line_to(20.0, 0.0);
+        outline.quad_to(24.0, 12.0, 10.0, 18.0);
+        outline.close();
+        outline.move_to(8.0, 4.0);
+        outline.line_to(8.0, 8.0);
+        outline.line_to(12.0, 8.0);
+        outline.line_to(12.0, 4.0);
+        outline.close();
+
+        let ts = Transform::from_scale(1.5, 1.5);
+        let msdf = outline.rasterize_msdf(ts, 4.0);
+        let mut transformed = outline.clone();
+        transformed.transform(ts);
+        for i in 0..msdf.height {
+            for j in 0..msdf.width {
+                let x = msdf.left as f32 + j as f32 + 0.5;
+                let y = msdf.top as f32 - i as f32 - 0.5;
+                let at = (i * msdf.width + j) as usize * 3;
+                let [r, g, b] = [msdf.pixels[at], msdf.pixels[at + 1], msdf.pixels[at + 2]];
+                let median = r.min(g).max(r.max(g).min(b));
+                let inside = transformed.contains(x, y, FillRule::NonZero);
+                assert_eq!(median >= 128, inside, "pixel at {}, {}", x, y);
+            }
+        }
+    }
+
+    #[test]
+    fn square() {
+        let mut outline = Outline::default();
+        outline.move_to(0.0, 0.0);
+        outline.line_to(20.0, 0.0);
+        outline.line_to(20.0, 20.0);
+        outline.line_to(0.0, 20.0);
+        outline.close();
+
+        let msdf = outline.rasterize_msdf(Transform::default(), 4.0);
+        assert_eq!((msdf.width, msdf.height), (24, 24));
+        // Corners are outside by the pseudo-distance to the sides.
+        assert_eq!(msdf.pixels[..3], [32, 32, 32]);
+        let center = (12 * msdf.width + 12) as usize * 3;
+        assert_eq!(msdf.pixels[center..center + 3], [255, 255, 255]);
+    }
+}
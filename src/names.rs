@@ -0,0 +1,58 @@
+use ttf_parser::{Face, GlyphId};
+
+use crate::Outline;
+
+impl Outline {
+    /// Returns a new outline for the glyph with the given name or `None` when there's
+    /// no such glyph, it has no outline or on error.
+    ///
+    /// Names come from the `post` or `CFF` table. Names of the `uniXXXX` and `uXXXXX`
+    /// forms that aren't in the font are looked up by their code point instead.
+    /// Every lookup scans the glyph names, so keep the glyph ids around when looking
+    /// up many outlines.
+    pub fn by_name(face: &Face, name: &str) -> Option<Self> {
+        Self::new(face, glyph_id_by_name(face, name)?)
+    }
+
+    /// Returns a new outline along with the glyph name, if the face has one, or
+    /// `None` when the glyph has no outline or on error.
+    pub fn new_with_name<'a>(face: &'a Face, glyph_id: GlyphId) -> Option<(Self, Option<&'a str>)> {
+        let outline = Self::new(face, glyph_id)?;
+        Some((outline, face.glyph_name(glyph_id)))
+    }
+}
+
+pub(crate) fn glyph_id_by_name(face: &Face, name: &str) -> Option<GlyphId> {
+    (0..face.number_of_glyphs())
+        .map(GlyphId)
+        .find(|&glyph_id| face.glyph_name(glyph_id) == Some(name))
+        .or_else(|| face.glyph_index(unicode_name_char(name)?))
+}
+
+// Returns the character of a `uniXXXX` or `uXXXX` to `uXXXXXX` name.
+fn unicode_name_char(name: &str) -> Option<char> {
+    let hex = if let Some(hex) = name.strip_prefix("uni") {
+        if hex.len() != 4 {
+            return None;
+        }
+
+        hex
+    } else {
+        let hex = name.strip_prefix('u')?;
+        if hex.len() < 4 || hex.len() > 6 {
+            return None;
+        }
+
+        hex
+    };
+
+    // Only uppercase digits are valid in these names.
+    if !hex
+        .bytes()
+        .all(|b| b.is_ascii_digit() || (b'A'..=b'F').contains(&b))
+    {
+        return None;
+    }
+
+    std::char::from_u32(u32::from_str_radix(hex, 16).ok()?)
+}
@@ -0,0 +1,143 @@
+// Readers for the common structures of the OpenType layout tables, `GPOS` and `GSUB`.
+
+use ttf_parser::{GlyphId, Tag};
+
+use crate::parser::{read_u16, read_u32};
+
+// A lookup with its type and the data of its subtables.
+pub(crate) struct Lookup<'a> {
+    pub(crate) kind: u16,
+    pub(crate) subtables: Vec<&'a [u8]>,
+}
+
+// Returns the lookups of every feature with one of the given tags, in lookup list
+// order and without duplicates. Scripts and languages aren't taken into account.
+//
+// Extension subtables are resolved, so `extension_kind` is the lookup type of
+// extensions in the table, i.e. 9 for `GPOS` and 7 for `GSUB`.
+pub(crate) fn feature_lookups<'a>(
+    data: &'a [u8],
+    features: &[Tag],
+    extension_kind: u16,
+) -> Vec<Lookup<'a>> {
+    parse_feature_lookups(data, features, extension_kind).unwrap_or_default()
+}
+
+fn parse_feature_lookups<'a>(
+    data: &'a [u8],
+    tags: &[Tag],
+    extension_kind: u16,
+) -> Option<Vec<Lookup<'a>>> {
+    let features = data.get(read_u16(data, 6)? as usize..)?;
+    let lookups = data.get(read_u16(data, 8)? as usize..)?;
+
+    let mut indices = Vec::new();
+    for i in 0..read_u16(features, 0)? as usize {
+        let record = 2 + i * 6;
+        let tag = features.get(record..record + 4)?;
+        if !tags.contains(&Tag::from_bytes(&[tag[0], tag[1], tag[2], tag[3]])) {
+            continue;
+        }
+
+        let offset = read_u16(features, record + 4)? as usize;
+        for j in 0..read_u16(features, offset + 2)? as usize {
+            indices.push(read_u16(features, offset + 4 + j * 2)?);
+        }
+    }
+
+    indices.sort_unstable();
+    indices.dedup();
+
+    let mut result = Vec::with_capacity(indices.len());
+    for index in indices {
+        let offset = read_u16(lookups, 2 + index as usize * 2)? as usize;
+        let lookup = lookups.get(offset..)?;
+        let mut kind = read_u16(lookup, 0)?;
+        let mut subtables = Vec::new();
+        for i in 0..read_u16(lookup, 4)? as usize {
+            let subtable = lookup.get(read_u16(lookup, 6 + i * 2)? as usize..)?;
+            if kind == extension_kind {
+                kind = read_u16(subtable, 2)?;
+                subtables.push(subtable.get(read_u32(subtable, 4)? as usize..)?);
+            } else {
+                subtables.push(subtable);
+            }
+        }
+
+        result.push(Lookup { kind, subtables });
+    }
+
+    Some(result)
+}
+
+// Returns the index of a glyph in a coverage table.
+pub(crate) fn coverage_index(data: &[u8], glyph_id: GlyphId) -> Option<u16> {
+    let glyph = glyph_id.0;
+    match read_u16(data, 0)? {
+        1 => {
+            let count = read_u16(data, 2)? as usize;
+            let (mut lo, mut hi) = (0, count);
+            while lo < hi {
+                let mid = (lo + hi) / 2;
+                let g = read_u16(data, 4 + mid * 2)?;
+                if g == glyph {
+                    return Some(mid as u16);
+                } else if g < glyph {
+                    lo = mid + 1;
+                } else {
+                    hi = mid;
+                }
+            }
+
+            None
+        }
+        2 => {
+            let record = find_range(data, glyph)?;
+            let start = read_u16(data, record)?;
+            let index = read_u16(data, record + 4)?;
+            Some(index + (glyph - start))
+        }
+        _ => None,
+    }
+}
+
+// Returns the class of a glyph in a class definition table, which is 0 for glyphs
+// that aren't in it.
+pub(crate) fn glyph_class(data: &[u8], glyph_id: GlyphId) -> u16 {
+    let glyph = glyph_id.0;
+    let class = match read_u16(data, 0) {
+        Some(1) => read_u16(data, 2).and_then(|start| {
+            let count = read_u16(data, 4)?;
+            let index = glyph.checked_sub(start)?;
+            if index >= count {
+                return None;
+            }
+
+            read_u16(data, 6 + index as usize * 2)
+        }),
+        Some(2) => find_range(data, glyph).and_then(|record| read_u16(data, record + 4)),
+        _ => None,
+    };
+
+    class.unwrap_or(0)
+}
+
+// Returns the offset of the range record that contains the glyph, in the ranges of a
+// format 2 coverage or class definition table.
+fn find_range(data: &[u8], glyph: u16) -> Option<usize> {
+    let count = read_u16(data, 2)? as usize;
+    let (mut lo, mut hi) = (0, count);
+    while lo < hi {
+        let mid = (lo + hi) / 2;
+        let record = 4 + mid * 6;
+        if glyph < read_u16(data, record)? {
+            hi = mid;
+        } else if glyph > read_u16(data, record + 2)? {
+            lo = mid + 1;
+        } else {
+            return Some(record);
+        }
+    }
+
+    None
+}
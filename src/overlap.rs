@@ -0,0 +1,457 @@
+use std::collections::HashMap;
+
+use crate::{Outline, Point};
+
+// The points to split a line at, along with their position on the line.
+type Splits = Vec<(f64, Point)>;
+
+// Intersections this close to an end point, relative to the edge length, snap to it.
+const SNAP_EPSILON: f64 = 1e-6;
+
+impl Outline {
+    /// Removes overlaps between and within contours.
+    ///
+    /// The outline is planarized and the contours are reassembled from the edges
+    /// that separate filled from unfilled areas under the non-zero fill rule. The
+    /// result has no overlaps, so it fills the same under both fill rules.
+    ///
+    /// Contours that don't intersect anything are kept as they are, but the ones
+    /// that do are rebuilt from lines, with curves flattened within `tolerance`.
+    /// Outer contours run in the direction of the outline format, i.e. clockwise
+    /// for TrueType and counter-clockwise for CFF in a y-up coordinate system.
+    pub fn remove_overlaps(&mut self, tolerance: f32) {
+        self.remove_overlaps_with(tolerance, |winding| winding != 0);
+    }
+
+    /// Removes overlaps, keeping the areas whose winding number passes `filled`.
+    pub(crate) fn remove_overlaps_with<F>(&mut self, tolerance: f32, filled: F)
+    where
+        F: Fn(i32) -> bool,
+    {
+        self.invalidate_bounds();
+
+        let polygons: Vec<Vec<Point>> = self
+            .contours
+            .iter()
+            .map(|c| {
+                let mut points = c.flatten_points(tolerance);
+                points.dedup();
+                points
+            })
+            .collect();
+        let graph = Graph::new(&polygons);
+
+        let mut contours = Vec::new();
+        for (i, c) in self.contours.iter().enumerate() {
+            if graph.touched[i] {
+                continue;
+            }
+
+            let edge = match graph.edges.iter().position(|e| e.contour == i) {
+                Some(edge) => edge,
+                // Degenerate contours don't fill anything.
+                None => continue,
+            };
+
+            let (left, right) = graph.sides(edge);
+            let inside_left = filled(left);
+            if inside_left == filled(right) {
+                continue;
+            }
+
+            // The edge direction follows the contour unless the key swapped its ends.
+            let forward = graph.edges[edge].forward;
+            let mut c = c.clone();
+            if (inside_left == forward) != self.cff {
+                c.reverse();
+            }
+
+            contours.push(c);
+        }
+
+        self.contours = contours;
+        self.components.clear();
+        for points in graph.trace(&filled) {
+            self.push_polygon(points);
+            // Traced contours have the inside on the left, i.e. run counter-clockwise.
+            if !self.cff {
+                self.contours.last_mut().unwrap().reverse();
+            }
+        }
+
+        // The result is a new set of contours, so their order becomes the source order.
+        for (i, c) in self.contours.iter_mut().enumerate() {
+            c.source_index = i;
+        }
+    }
+}
+
+// An undirected edge of the planar graph, which merges coincident edges.
+struct Edge {
+    a: usize,
+    b: usize,
+    // The number of times the edge is traversed from `a` to `b` minus the number of
+    // times it's traversed in the other direction.
+    delta: i32,
+    // The contour the edge came from first.
+    contour: usize,
+    // Whether the first contour traverses the edge from `a` to `b`.
+    forward: bool,
+}
+
+struct Graph {
+    vertices: Vec<Point>,
+    edges: Vec<Edge>,
+    // Whether a contour intersects or shares edges with any contour.
+    touched: Vec<bool>,
+}
+
+impl Graph {
+    fn new(polygons: &[Vec<Point>]) -> Self {
+        // All polygon edges with their contour index and the points to split them at.
+        let mut lines: Vec<(Point, Point, usize, Splits)> = Vec::new();
+        for (i, points) in polygons.iter().enumerate() {
+            if points.len() < 3 {
+                continue;
+            }
+
+            for (j, &p0) in points.iter().enumerate() {
+                let p1 = points[(j + 1) % points.len()];
+                lines.push((p0, p1, i, Vec::new()));
+            }
+        }
+
+        for i in 0..lines.len() {
+            for j in i + 1..lines.len() {
+                let (p0, p1) = (lines[i].0, lines[i].1);
+                let (q0, q1) = (lines[j].0, lines[j].1);
+                if p0.x.max(p1.x) < q0.x.min(q1.x)
+                    || q0.x.max(q1.x) < p0.x.min(p1.x)
+                    || p0.y.max(p1.y) < q0.y.min(q1.y)
+                    || q0.y.max(q1.y) < p0.y.min(p1.y)
+                {
+                    continue;
+                }
+
+                let (splits_i, splits_j) = intersect(p0, p1, q0, q1);
+                lines[i].3.extend(splits_i);
+                lines[j].3.extend(splits_j);
+            }
+        }
+
+        let mut graph = Graph {
+            vertices: Vec::new(),
+            edges: Vec::new(),
+            touched: vec![false; polygons.len()],
+        };
+        let mut vertex_ids = HashMap::new();
+        let mut edge_ids = HashMap::new();
+        for (p0, p1, contour, mut splits) in lines {
+            if !splits.is_empty() {
+                graph.touched[contour] = true;
+            }
+
+            splits.sort_by(|a, b| a.0.total_cmp(&b.0));
+            let points = std::iter::once(p0)
+                .chain(splits.into_iter().map(|(_, p)| p))
+                .chain(std::iter::once(p1));
+            let mut prev: Option<usize> = None;
+            for p in points {
+                let id = *vertex_ids.entry(key(p)).or_insert_with(|| {
+                    graph.vertices.push(p);
+                    graph.vertices.len() - 1
+                });
+                if let Some(prev) = prev.replace(id) {
+                    if prev != id {
+                        graph.add_edge(&mut edge_ids, prev, id, contour);
+                    }
+                }
+            }
+        }
+
+        graph
+    }
+
+    fn add_edge(
+        &mut self,
+        edge_ids: &mut HashMap<(usize, usize), usize>,
+        from: usize,
+        to: usize,
+        contour: usize,
+    ) {
+        let (a, b, delta) = if from < to {
+            (from, to, 1)
+        } else {
+            (to, from, -1)
+        };
+        match edge_ids.get(&(a, b)) {
+            Some(&id) => {
+                let edge = &mut self.edges[id];
+                edge.delta += delta;
+                self.touched[edge.contour] = true;
+                self.touched[contour] = true;
+            }
+            None => {
+                edge_ids.insert((a, b), self.edges.len());
+                self.edges.push(Edge {
+                    a,
+                    b,
+                    delta,
+                    contour,
+                    forward: delta > 0,
+                });
+            }
+        }
+    }
+
+    /// Returns the winding numbers of the areas to the left and right of an edge,
+    /// looking from `a` to `b`.
+    fn sides(&self, edge: usize) -> (i32, i32) {
+        let e = &self.edges[edge];
+        let a = self.vertices[e.a];
+        let b = self.vertices[e.b];
+        let m = a.lerp(b, 0.5);
+        let d = e.delta;
+        // Cast a ray away from the edge, which is only crossed by other edges since
+        // the graph is planar. Crossing an edge from its right to its left side adds
+        // its delta to the winding number.
+        let (left, right) = if a.y != b.y {
+            let w = self.winding(m, edge, false);
+            if b.y > a.y {
+                (w + d, w)
+            } else {
+                (w, w - d)
+            }
+        } else {
+            let w = self.winding(m, edge, true);
+            if b.x > a.x {
+                (w, w - d)
+            } else {
+                (w + d, w)
+            }
+        };
+
+        (left, right)
+    }
+
+    // Returns the winding number at `p`, ignoring the given edge, by casting a ray in
+    // the positive x or y direction.
+    fn winding(&self, p: Point, ignore: usize, vertical: bool) -> i32 {
+        let mut winding = 0;
+        for (i, e) in self.edges.iter().enumerate() {
+            if i == ignore || e.delta == 0 {
+                continue;
+            }
+
+            let mut a = self.vertices[e.a];
+            let mut b = self.vertices[e.b];
+            let mut p = p;
+            let mut delta = e.delta;
+            if vertical {
+                // Mirror along the diagonal so the same code casts a vertical ray,
+                // which flips the orientation.
+                std::mem::swap(&mut a.x, &mut a.y);
+                std::mem::swap(&mut b.x, &mut b.y);
+                std::mem::swap(&mut p.x, &mut p.y);
+                delta = -delta;
+            }
+
+            if (a.y <= p.y) != (b.y <= p.y) {
+                let x = a.x + (p.y - a.y) * (b.x - a.x) / (b.y - a.y);
+                if x > p.x {
+                    winding += if b.y > a.y { delta } else { -delta };
+                }
+            }
+        }
+
+        winding
+    }
+
+    /// Traces the filled areas of the edges of touched contours into polygons with
+    /// the inside on their left.
+    fn trace<F: Fn(i32) -> bool>(&self, filled: F) -> Vec<Vec<Point>> {
+        let mut directed: Vec<(usize, usize)> = Vec::new();
+        for (i, e) in self.edges.iter().enumerate() {
+            if e.delta == 0 || !self.touched[e.contour] {
+                continue;
+            }
+
+            let (left, right) = self.sides(i);
+            match (filled(left), filled(right)) {
+                (true, false) => directed.push((e.a, e.b)),
+                (false, true) => directed.push((e.b, e.a)),
+                _ => {}
+            }
+        }
+
+        let mut outgoing = vec![Vec::new(); self.vertices.len()];
+        for (i, &(from, _)) in directed.iter().enumerate() {
+            outgoing[from].push(i);
+        }
+
+        let mut used = vec![false; directed.len()];
+        let mut polygons = Vec::new();
+        for start in 0..directed.len() {
+            if used[start] {
+                continue;
+            }
+
+            let mut points = Vec::new();
+            let mut current = start;
+            loop {
+                used[current] = true;
+                let (from, to) = directed[current];
+                points.push(self.vertices[from]);
+
+                // Turn as far left as possible to stay on the boundary of a single
+                // filled area, which splits areas that only touch at a vertex.
+                let back = self.vertices[from] - self.vertices[to];
+                let next = outgoing[to]
+                    .iter()
+                    .copied()
+                    .filter(|&i| !used[i] || i == start)
+                    .min_by(|&i, &j| {
+                        let angle_i = clockwise_angle(back, self.direction(directed[i]));
+                        let angle_j = clockwise_angle(back, self.direction(directed[j]));
+                        angle_i.total_cmp(&angle_j)
+                    });
+                match next {
+                    Some(next) if next != start => current = next,
+                    _ => break,
+                }
+            }
+
+            remove_collinear(&mut points);
+            if points.len() >= 3 {
+                polygons.push(points);
+            }
+        }
+
+        polygons
+    }
+
+    #[inline]
+    fn direction(&self, (from, to): (usize, usize)) -> Point {
+        self.vertices[to] - self.vertices[from]
+    }
+}
+
+// Returns the points to split each line at where they intersect or overlap.
+fn intersect(p0: Point, p1: Point, q0: Point, q1: Point) -> (Splits, Splits) {
+    let mut splits_p = Vec::new();
+    let mut splits_q = Vec::new();
+
+    let (px, py) = (p0.x as f64, p0.y as f64);
+    let (rx, ry) = (p1.x as f64 - px, p1.y as f64 - py);
+    let (qx, qy) = (q0.x as f64, q0.y as f64);
+    let (sx, sy) = (q1.x as f64 - qx, q1.y as f64 - qy);
+    let r_len2 = rx * rx + ry * ry;
+    let s_len2 = sx * sx + sy * sy;
+    if r_len2 == 0.0 || s_len2 == 0.0 {
+        return (splits_p, splits_q);
+    }
+
+    let cross = |ax: f64, ay: f64, bx: f64, by: f64| ax * by - ay * bx;
+    let rxs = cross(rx, ry, sx, sy);
+    let (dx, dy) = (qx - px, qy - py);
+    if rxs.abs() <= SNAP_EPSILON * (r_len2 * s_len2).sqrt() {
+        // Parallel lines only matter when they are collinear and overlap, in which
+        // case each line is split at the end points of the other one.
+        if cross(dx, dy, rx, ry).abs() > SNAP_EPSILON * r_len2 {
+            return (splits_p, splits_q);
+        }
+
+        let split = |t: f64, p: Point, splits: &mut Splits| {
+            if t > SNAP_EPSILON && t < 1.0 - SNAP_EPSILON {
+                splits.push((t, p));
+            }
+        };
+        for &q in &[q0, q1] {
+            let t = ((q.x as f64 - px) * rx + (q.y as f64 - py) * ry) / r_len2;
+            split(t, q, &mut splits_p);
+        }
+
+        for &p in &[p0, p1] {
+            let u = ((p.x as f64 - qx) * sx + (p.y as f64 - qy) * sy) / s_len2;
+            split(u, p, &mut splits_q);
+        }
+
+        return (splits_p, splits_q);
+    }
+
+    let t = cross(dx, dy, sx, sy) / rxs;
+    let u = cross(dx, dy, rx, ry) / rxs;
+    let range = -SNAP_EPSILON..=1.0 + SNAP_EPSILON;
+    if !range.contains(&t) || !range.contains(&u) {
+        return (splits_p, splits_q);
+    }
+
+    let snap = |t: f64| {
+        if t <= SNAP_EPSILON {
+            0.0
+        } else if t >= 1.0 - SNAP_EPSILON {
+            1.0
+        } else {
+            t
+        }
+    };
+    let t = snap(t);
+    let u = snap(u);
+    // Both lines must be split at the exact same point to share a vertex.
+    let p = if t == 0.0 {
+        p0
+    } else if t == 1.0 {
+        p1
+    } else if u == 0.0 {
+        q0
+    } else if u == 1.0 {
+        q1
+    } else {
+        Point::new((px + rx * t) as f32, (py + ry * t) as f32)
+    };
+
+    if t > 0.0 && t < 1.0 {
+        splits_p.push((t, p));
+    }
+
+    if u > 0.0 && u < 1.0 {
+        splits_q.push((u, p));
+    }
+
+    (splits_p, splits_q)
+}
+
+#[inline]
+fn key(p: Point) -> (u32, u32) {
+    // Adding zero turns negative zero into positive zero.
+    ((p.x + 0.0).to_bits(), (p.y + 0.0).to_bits())
+}
+
+// Returns the angle from `a` to `b` in the clockwise direction, in `(0, 2 * PI]`.
+fn clockwise_angle(a: Point, b: Point) -> f32 {
+    let angle = -(a.x * b.y - a.y * b.x).atan2(a.x * b.x + a.y * b.y);
+    if angle <= 0.0 {
+        angle + 2.0 * std::f32::consts::PI
+    } else {
+        angle
+    }
+}
+
+// Removes points that lie on a straight line between their neighbors, e.g. the ones
+// left over from splitting collinear edges.
+fn remove_collinear(points: &mut Vec<Point>) {
+    let mut i = 0;
+    while points.len() >= 3 && i < points.len() {
+        let prev = points[(i + points.len() - 1) % points.len()];
+        let next = points[(i + 1) % points.len()];
+        let d1 = points[i] - prev;
+        let d2 = next - points[i];
+        let cross = d1.x * d2.y - d1.y * d2.x;
+        let dot = d1.x * d2.x + d1.y * d2.y;
+        if cross.abs() <= 1e-6 * d1.length() * d2.length() && dot > 0.0 {
+            points.remove(i);
+        } else {
+            i += 1;
+        }
+    }
+}
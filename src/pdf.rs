@@ -0,0 +1,70 @@
+use crate::{write_number, Outline};
+
+impl Outline {
+    /// Returns the outline as PDF content stream path operators.
+    ///
+    /// Uses the `m`, `l`, `c` and `h` operators, one per line. Quadratic curves are
+    /// converted to cubic ones since PDF has no quadratic operator. No painting
+    /// operator is appended, so follow it with e.g. `f` to fill the path.
+    pub fn to_pdf_path(&self) -> String {
+        let mut writer = PdfPathWriter {
+            data: String::new(),
+            last: (0.0, 0.0),
+        };
+        self.emit(&mut writer);
+        writer.data
+    }
+}
+
+struct PdfPathWriter {
+    data: String,
+    last: (f32, f32),
+}
+
+impl PdfPathWriter {
+    fn push(&mut self, coords: &[f32], op: &str) {
+        for n in coords {
+            write_number(&mut self.data, *n, None);
+            self.data.push(' ');
+        }
+
+        self.data.push_str(op);
+        self.data.push('\n');
+        if coords.len() >= 2 {
+            self.last = (coords[coords.len() - 2], coords[coords.len() - 1]);
+        }
+    }
+}
+
+impl ttf_parser::OutlineBuilder for PdfPathWriter {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.push(&[x, y], "m");
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.push(&[x, y], "l");
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let (x0, y0) = self.last;
+        self.push(
+            &[
+                x0 + 2.0 / 3.0 * (x1 - x0),
+                y0 + 2.0 / 3.0 * (y1 - y0),
+                x + 2.0 / 3.0 * (x1 - x),
+                y + 2.0 / 3.0 * (y1 - y),
+                x,
+                y,
+            ],
+            "c",
+        );
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        self.push(&[x1, y1, x2, y2, x, y], "c");
+    }
+
+    fn close(&mut self) {
+        self.push(&[], "h");
+    }
+}
@@ -0,0 +1,494 @@
+use crate::{BBox, FillRule, Outline, Transform};
+
+// The number of scanlines sampled per row of pixels.
+const SUBSCANLINES: usize = 16;
+
+// The maximum distance between flattened curves and the curves, in pixels.
+const TOLERANCE: f32 = 0.1;
+
+/// An 8-bit coverage raster of an outline.
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Raster {
+    /// The width in pixels.
+    pub width: u32,
+    /// The height in pixels.
+    pub height: u32,
+    /// The coverage of each pixel, from 0 outside to 255 inside the outline, row by
+    /// row from the top.
+    pub pixels: Vec<u8>,
+    /// The position of the left edge, in pixels from the origin.
+    pub left: i32,
+    /// The position of the top edge, in pixels up from the origin.
+    pub top: i32,
+}
+
+/// A 1-bit raster of an outline.
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MonoRaster {
+    /// The width in pixels.
+    pub width: u32,
+    /// The height in pixels.
+    pub height: u32,
+    /// The number of bytes per row.
+    pub pitch: u32,
+    /// The pixels, one bit each with the most significant bit leftmost, row by row
+    /// from the top. Rows are padded to whole bytes.
+    pub pixels: Vec<u8>,
+    /// The position of the left edge, in pixels from the origin.
+    pub left: i32,
+    /// The position of the top edge, in pixels up from the origin.
+    pub top: i32,
+}
+
+impl MonoRaster {
+    /// Returns `true` if the pixel at `x` from the left and `y` from the top is set.
+    #[inline]
+    pub fn is_set(&self, x: u32, y: u32) -> bool {
+        if x >= self.width || y >= self.height {
+            return false;
+        }
+
+        let byte = self.pixels[(y * self.pitch + x / 8) as usize];
+        byte & (0x80 >> (x % 8)) != 0
+    }
+
+    fn set(&mut self, x: i32, y: i32) {
+        let x = x.max(0).min(self.width as i32 - 1) as u32;
+        let y = y.max(0).min(self.height as i32 - 1) as u32;
+        self.pixels[(y * self.pitch + x / 8) as usize] |= 0x80 >> (x % 8);
+    }
+}
+
+impl Outline {
+    /// Returns the anti-aliased coverage of the outline with `ts` applied, which
+    /// should map font units to pixels, e.g. `Transform::from_scale(s, s)` where `s`
+    /// is the pixels per em divided by the units per em.
+    ///
+    /// The raster covers the transformed control box and is empty for empty outlines.
+    /// All contours are treated as closed. Coverage is exact horizontally and sampled
+    /// on 16 scanlines per row of pixels.
+    pub fn rasterize(&self, ts: Transform, fill_rule: FillRule) -> Raster {
+        let edges = Edges::new(self, ts);
+        let (left, top, width, height) = match edges.bounds() {
+            Some(bounds) => bounds,
+            None => return Raster::default(),
+        };
+
+        let mut pixels = vec![0; width as usize * height as usize];
+        let mut row = vec![0.0; width as usize];
+        for (i, pixels) in pixels.chunks_mut(width as usize).enumerate() {
+            edges.coverage(top - i as i32, left, fill_rule, &mut row, pixels);
+        }
+
+        Raster {
+            width,
+            height,
+            pixels,
+            left,
+            top,
+        }
+    }
+
+    /// Returns the bilevel raster of the outline with `ts` applied, see `rasterize`.
+    ///
+    /// A pixel is set when its center is inside the outline. With `dropout_control`
+    /// stems and curves thinner than a pixel don't break up: on every row and column
+    /// where a span of the outline misses all pixel centers, the pixel closest to the
+    /// middle of the span is set, like the TrueType smart dropout mode.
+    pub fn rasterize_mono(
+        &self,
+        ts: Transform,
+        fill_rule: FillRule,
+        dropout_control: bool,
+    ) -> MonoRaster {
+        let edges = Edges::new(self, ts);
+        let (left, top, width, height) = match edges.bounds() {
+            Some(bounds) => bounds,
+            None => return MonoRaster::default(),
+        };
+
+        let pitch = width.div_ceil(8);
+        let mut raster = MonoRaster {
+            width,
+            height,
+            pitch,
+            pixels: vec![0; pitch as usize * height as usize],
+            left,
+            top,
+        };
+
+        for i in 0..height as i32 {
+            let y = (top - i) as f32 - 0.5;
+            edges.spans(y, fill_rule, |x0, x1| {
+                // The pixels with their centers within the span.
+                let start = (x0 - left as f32 - 0.5).ceil() as i32;
+                let end = (x1 - left as f32 - 0.5).ceil() as i32;
+                for j in start..end {
+                    raster.set(j, i);
+                }
+
+                if start == end && x0 < x1 && dropout_control {
+                    raster.set(((x0 + x1) * 0.5 - left as f32).floor() as i32, i);
+                }
+            });
+        }
+
+        if dropout_control {
+            // Swapping x and y turns columns into rows.
+            let columns = Edges::new(self, ts.then(&Transform::new(0.0, 1.0, 1.0, 0.0, 0.0, 0.0)));
+            for j in 0..width as i32 {
+                let x = (left + j) as f32 + 0.5;
+                columns.spans(x, fill_rule, |y0, y1| {
+                    let start = (y0 - 0.5).ceil();
+                    let end = (y1 - 0.5).ceil();
+                    if start == end && y0 < y1 {
+                        raster.set(j, top - 1 - ((y0 + y1) * 0.5).floor() as i32);
+                    }
+                });
+            }
+        }
+
+        raster
+    }
+}
+
+/// A horizontal run of pixels with the same coverage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Span {
+    /// The row, in pixels down from the origin, so the row just above the origin is
+    /// -1.
+    pub y: i32,
+    /// The first pixel, in pixels from the origin.
+    pub x_start: i32,
+    /// The pixel after the last one.
+    pub x_end: i32,
+    /// The coverage of the pixels, from 1 to 255.
+    pub coverage: u8,
+}
+
+/// An iterator over the spans of an outline, see `Outline::spans`.
+#[derive(Debug)]
+pub struct Spans {
+    edges: Edges,
+    fill_rule: FillRule,
+    left: i32,
+    top: i32,
+    height: u32,
+    // The next row and its coverage.
+    y: u32,
+    row: Vec<f32>,
+    pixels: Vec<u8>,
+    // The next pixel of the current row, which is past the end before the first row.
+    x: usize,
+}
+
+impl Iterator for Spans {
+    type Item = Span;
+
+    fn next(&mut self) -> Option<Span> {
+        loop {
+            while self.x < self.pixels.len() && self.pixels[self.x] == 0 {
+                self.x += 1;
+            }
+
+            if self.x < self.pixels.len() {
+                let start = self.x;
+                let coverage = self.pixels[start];
+                while self.x < self.pixels.len() && self.pixels[self.x] == coverage {
+                    self.x += 1;
+                }
+
+                return Some(Span {
+                    y: self.y as i32 - 1 - self.top,
+                    x_start: self.left + start as i32,
+                    x_end: self.left + self.x as i32,
+                    coverage,
+                });
+            }
+
+            if self.y >= self.height {
+                return None;
+            }
+
+            let top = self.top - self.y as i32;
+            self.edges.coverage(
+                top,
+                self.left,
+                self.fill_rule,
+                &mut self.row,
+                &mut self.pixels,
+            );
+            self.y += 1;
+            self.x = 0;
+        }
+    }
+}
+
+impl Outline {
+    /// Returns the spans of pixels covered by the outline with `ts` applied, row by
+    /// row from the top and from left to right within each row.
+    ///
+    /// The coverage is the same as the one of `rasterize`, without allocating the
+    /// whole raster. Uncovered pixels are skipped.
+    pub fn spans(&self, ts: Transform, fill_rule: FillRule) -> Spans {
+        let edges = Edges::new(self, ts);
+        let (left, top, width, height) = edges.bounds().unwrap_or((0, 0, 0, 0));
+        Spans {
+            edges,
+            fill_rule,
+            left,
+            top,
+            height,
+            y: 0,
+            row: vec![0.0; width as usize],
+            pixels: vec![0; width as usize],
+            x: width as usize,
+        }
+    }
+
+    /// Returns the covered fraction of each cell of a grid over `rect`, in font
+    /// units, with `columns` by `rows` cells, row by row from the top.
+    ///
+    /// Each cell is sampled at the centers of a `supersampling` by `supersampling`
+    /// grid of points, so the fractions are multiples of one over its square. This
+    /// is e.g. a darkness map of a glyph for `rect` set to its advance and the
+    /// ascender and descender.
+    pub fn coverage_grid(
+        &self,
+        rect: BBox,
+        columns: u32,
+        rows: u32,
+        supersampling: u32,
+        fill_rule: FillRule,
+    ) -> Vec<f32> {
+        let mut counts = vec![0u32; columns as usize * rows as usize];
+        let k = supersampling.max(1);
+        let (width, height) = (columns * k, rows * k);
+        if counts.is_empty() || rect.width() <= 0.0 || rect.height() <= 0.0 {
+            return vec![0.0; counts.len()];
+        }
+
+        // Samples are at the centers of the pixels of the transformed outline.
+        let ts = Transform::from_translate(-rect.x_min, -rect.y_min).then(&Transform::from_scale(
+            width as f32 / rect.width(),
+            height as f32 / rect.height(),
+        ));
+        let edges = Edges::new(self, ts);
+        for i in 0..height {
+            let cells = &mut counts[(i / k * columns) as usize..][..columns as usize];
+            edges.spans((height - i) as f32 - 0.5, fill_rule, |x0, x1| {
+                let start = (x0 - 0.5).ceil().max(0.0) as u32;
+                let end = ((x1 - 0.5).ceil().max(0.0) as u32).min(width);
+                for j in start..end {
+                    cells[(j / k) as usize] += 1;
+                }
+            });
+        }
+
+        let samples = (k * k) as f32;
+        counts.into_iter().map(|n| n as f32 / samples).collect()
+    }
+}
+
+// Adds the coverage of a span, in pixels from the left edge, to a row.
+fn accumulate(row: &mut [f32], x0: f32, x1: f32) {
+    let x0 = x0.max(0.0);
+    let x1 = x1.min(row.len() as f32);
+    if x0 >= x1 {
+        return;
+    }
+
+    let i0 = x0 as usize;
+    let i1 = x1 as usize;
+    if i0 == i1 {
+        row[i0] += x1 - x0;
+        return;
+    }
+
+    row[i0] += (i0 + 1) as f32 - x0;
+    for c in &mut row[i0 + 1..i1] {
+        *c += 1.0;
+    }
+
+    if i1 < row.len() {
+        row[i1] += x1 - i1 as f32;
+    }
+}
+
+// A line of a flattened outline, from bottom to top, with the winding of its
+// original direction.
+#[derive(Debug)]
+struct Edge {
+    x0: f32,
+    y0: f32,
+    x1: f32,
+    y1: f32,
+    winding: i32,
+}
+
+// The lines of an outline flattened in pixels.
+#[derive(Debug)]
+pub(crate) struct Edges {
+    edges: Vec<Edge>,
+    x_min: f32,
+    y_min: f32,
+    x_max: f32,
+    y_max: f32,
+}
+
+impl Edges {
+    pub(crate) fn new(outline: &Outline, ts: Transform) -> Self {
+        let mut edges = Edges {
+            edges: Vec::new(),
+            x_min: f32::MAX,
+            y_min: f32::MAX,
+            x_max: f32::MIN,
+            y_max: f32::MIN,
+        };
+
+        for c in &outline.contours {
+            let mut c = c.clone();
+            c.transform(ts);
+            let points = c.flatten_points(TOLERANCE);
+            for (i, &p0) in points.iter().enumerate() {
+                let p1 = points[(i + 1) % points.len()];
+                edges.x_min = edges.x_min.min(p0.x);
+                edges.y_min = edges.y_min.min(p0.y);
+                edges.x_max = edges.x_max.max(p0.x);
+                edges.y_max = edges.y_max.max(p0.y);
+                if p0.y < p1.y {
+                    edges.push(p0.x, p0.y, p1.x, p1.y, 1);
+                } else if p1.y < p0.y {
+                    edges.push(p1.x, p1.y, p0.x, p0.y, -1);
+                }
+            }
+        }
+
+        edges
+    }
+
+    fn push(&mut self, x0: f32, y0: f32, x1: f32, y1: f32, winding: i32) {
+        self.edges.push(Edge {
+            x0,
+            y0,
+            x1,
+            y1,
+            winding,
+        });
+    }
+
+    // Returns the left, top, width and height of the pixels touched by the edges,
+    // or `None` when there are none.
+    pub(crate) fn bounds(&self) -> Option<(i32, i32, u32, u32)> {
+        if self.x_min > self.x_max {
+            return None;
+        }
+
+        let left = self.x_min.floor() as i32;
+        let bottom = self.y_min.floor() as i32;
+        let right = (self.x_max.ceil() as i32).max(left + 1);
+        let top = (self.y_max.ceil() as i32).max(bottom + 1);
+        Some((left, top, (right - left) as u32, (top - bottom) as u32))
+    }
+
+    // Sets the coverage of the row of pixels below `top` starting at `left`, using
+    // `row` for the sums of the scanlines.
+    fn coverage(
+        &self,
+        top: i32,
+        left: i32,
+        fill_rule: FillRule,
+        row: &mut [f32],
+        pixels: &mut [u8],
+    ) {
+        row.iter_mut().for_each(|c| *c = 0.0);
+        for i in 0..SUBSCANLINES {
+            let y = top as f32 - (i as f32 + 0.5) / SUBSCANLINES as f32;
+            self.spans(y, fill_rule, |x0, x1| {
+                accumulate(row, x0 - left as f32, x1 - left as f32)
+            });
+        }
+
+        for (p, c) in pixels.iter_mut().zip(row.iter()) {
+            let coverage = c / SUBSCANLINES as f32;
+            *p = (coverage.min(1.0) * 255.0 + 0.5) as u8;
+        }
+    }
+
+    // Calls `f` with the start and end of each span inside the edges on the
+    // horizontal line at `y`, from left to right.
+    pub(crate) fn spans<F: FnMut(f32, f32)>(&self, y: f32, fill_rule: FillRule, mut f: F) {
+        // Half open in y, so shared end points are only counted once.
+        let mut crossings: Vec<(f32, i32)> = self
+            .edges
+            .iter()
+            .filter(|e| e.y0 <= y && y < e.y1)
+            .map(|e| {
+                let t = (y - e.y0) / (e.y1 - e.y0);
+                (e.x0 + (e.x1 - e.x0) * t, e.winding)
+            })
+            .collect();
+        crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let mut winding = 0;
+        let mut start = 0.0;
+        for (x, w) in crossings {
+            let inside = is_inside(winding, fill_rule);
+            winding += w;
+            match (inside, is_inside(winding, fill_rule)) {
+                (false, true) => start = x,
+                (true, false) => f(start, x),
+                _ => {}
+            }
+        }
+    }
+}
+
+#[inline]
+fn is_inside(winding: i32, fill_rule: FillRule) -> bool {
+    match fill_rule {
+        FillRule::NonZero => winding != 0,
+        FillRule::EvenOdd => winding % 2 != 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{FillRule, Outline, Transform};
+
+    fn rect(outline: &mut Outline, x0: f32, y0: f32, x1: f32, y1: f32) {
+        outline.move_to(x0, y0);
+        outline.line_to(x1, y0);
+        outline.line_to(x1, y1);
+        outline.line_to(x0, y1);
+        outline.close();
+    }
+
+    #[test]
+    fn mono_dropout() {
+        // A vertical stem and a horizontal bar that miss all pixel centers.
+        let mut outline = Outline::default();
+        rect(&mut outline, 2.1, 0.0, 2.4, 10.0);
+        rect(&mut outline, 4.0, 5.1, 12.0, 5.4);
+
+        let mono = outline.rasterize_mono(Transform::default(), FillRule::NonZero, false);
+        assert!(mono.pixels.iter().all(|&byte| byte == 0));
+
+        let mono = outline.rasterize_mono(Transform::default(), FillRule::NonZero, true);
+        let set = |x: i32, y: i32| mono.is_set((x - mono.left) as u32, (mono.top - 1 - y) as u32);
+        for y in 0..10 {
+            assert!(set(2, y), "stem row {}", y);
+            assert!(!set(1, y) && !set(3, y));
+        }
+        for x in 4..12 {
+            assert!(set(x, 5), "bar column {}", x);
+            assert!(!set(x, 4) && !set(x, 6));
+        }
+
+        let count: u32 = mono.pixels.iter().map(|byte| byte.count_ones()).sum();
+        assert_eq!(count, 18);
+    }
+}
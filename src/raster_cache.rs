@@ -0,0 +1,95 @@
+use std::collections::hash_map::{Entry, HashMap};
+
+use ttf_parser::{Face, GlyphId};
+
+use crate::{FillRule, Outline, Raster, Transform};
+
+/// A cache of glyph rasters by glyph, size and horizontal subpixel position, so
+/// repeated glyphs are rasterized once.
+#[derive(Debug, Clone)]
+pub struct RasterCache<'a> {
+    face: Face<'a>,
+    phases: u32,
+    rasters: HashMap<(u16, u32, u32), Raster>,
+    hits: u64,
+    misses: u64,
+}
+
+impl<'a> RasterCache<'a> {
+    /// Returns an empty cache for a face, which positions glyphs at `phases` evenly
+    /// spaced horizontal offsets within a pixel, e.g. 4, or 1 to always rasterize
+    /// glyphs at whole pixels.
+    pub fn new(face: Face<'a>, phases: u32) -> Self {
+        RasterCache {
+            face,
+            phases: phases.max(1),
+            rasters: HashMap::new(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Returns the raster of a glyph at a size in pixels per em with its origin at `x`
+    /// pixels, rasterizing it unless it's cached.
+    ///
+    /// The fraction of `x` is rounded down to a phase, and the raster is placed at
+    /// `x.floor()` plus its `left` edge. Glyphs without outlines have empty rasters.
+    pub fn get(&mut self, glyph_id: GlyphId, size: f32, x: f32) -> &Raster {
+        let phase = ((x - x.floor()) * self.phases as f32) as u32;
+        let phase = phase.min(self.phases - 1);
+        match self.rasters.entry((glyph_id.0, size.to_bits(), phase)) {
+            Entry::Occupied(entry) => {
+                self.hits += 1;
+                entry.into_mut()
+            }
+            Entry::Vacant(entry) => {
+                self.misses += 1;
+                let scale = size / f32::from(self.face.units_per_em().unwrap_or(1000));
+                let offset = phase as f32 / self.phases as f32;
+                let ts = Transform::from_scale(scale, scale)
+                    .then(&Transform::from_translate(offset, 0.0));
+                let raster = Outline::new(&self.face, glyph_id)
+                    .map(|outline| outline.rasterize(ts, FillRule::NonZero))
+                    .unwrap_or_default();
+                entry.insert(raster)
+            }
+        }
+    }
+
+    /// Returns the face.
+    #[inline]
+    pub fn face(&self) -> &Face<'a> {
+        &self.face
+    }
+
+    /// Returns the number of cached rasters.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.rasters.len()
+    }
+
+    /// Returns `true` if no rasters are cached.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.rasters.is_empty()
+    }
+
+    /// Returns the number of times `get` found a cached raster.
+    #[inline]
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// Returns the number of times `get` had to rasterize a glyph.
+    #[inline]
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    /// Removes all rasters and resets the statistics.
+    pub fn clear(&mut self) {
+        self.rasters.clear();
+        self.hits = 0;
+        self.misses = 0;
+    }
+}
@@ -0,0 +1,59 @@
+use crate::geom::Segment;
+use crate::{write_number, Outline};
+
+impl Outline {
+    /// Returns the outline as a FontForge SFD `SplineSet`.
+    ///
+    /// Quadratic curves are converted to cubic ones and every contour is closed, since
+    /// that is what FontForge expects for filled glyphs.
+    pub fn to_sfd_spline_set(&self) -> String {
+        let mut sfd = String::from("SplineSet\n");
+        for c in &self.contours {
+            let start = match c.segments().next() {
+                Some(segment) => segment.start(),
+                None => continue,
+            };
+
+            push_point(&mut sfd, &[start.x, start.y], "m 1");
+            let mut last = start;
+            for segment in c.segments() {
+                match segment {
+                    Segment::Line(_, p) => push_point(&mut sfd, &[p.x, p.y], "l 1"),
+                    Segment::Quad(p0, p1, p) => {
+                        let c1 = p0.lerp(p1, 2.0 / 3.0);
+                        let c2 = p.lerp(p1, 2.0 / 3.0);
+                        push_point(&mut sfd, &[c1.x, c1.y, c2.x, c2.y, p.x, p.y], "c 0");
+                    }
+                    Segment::Cubic(_, p1, p2, p) => {
+                        push_point(&mut sfd, &[p1.x, p1.y, p2.x, p2.y, p.x, p.y], "c 0");
+                    }
+                }
+
+                last = segment.end();
+            }
+
+            // FontForge treats a contour as closed when it ends at its start point.
+            if last != start {
+                push_point(&mut sfd, &[start.x, start.y], "l 1");
+            }
+        }
+
+        sfd.push_str("EndSplineSet\n");
+        sfd
+    }
+}
+
+fn push_point(sfd: &mut String, coords: &[f32], op: &str) {
+    // Only the points that start a contour are not indented.
+    if !op.starts_with('m') {
+        sfd.push(' ');
+    }
+
+    for n in coords {
+        write_number(sfd, *n, None);
+        sfd.push(' ');
+    }
+
+    sfd.push_str(op);
+    sfd.push('\n');
+}
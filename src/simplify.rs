@@ -0,0 +1,159 @@
+use crate::geom::Segment;
+use crate::{Outline, Point};
+
+impl Outline {
+    /// Removes degenerate and redundant segments.
+    ///
+    /// Lines shorter than `epsilon` are removed, curves whose control points are
+    /// within `epsilon` of the chord become lines, and consecutive lines that are
+    /// collinear within `epsilon` are merged.
+    pub fn simplify(&mut self, epsilon: f32) {
+        self.invalidate_bounds();
+        for c in &mut self.contours {
+            let start = match c.points.first() {
+                Some(p) => *p,
+                None => continue,
+            };
+
+            let mut segments: Vec<Segment> = Vec::new();
+            // The points merged into the last line, which must stay within epsilon.
+            let mut merged: Vec<Point> = Vec::new();
+            for segment in c.segments() {
+                let segment = match segment {
+                    Segment::Quad(p0, p1, p2) if is_flat(p0, &[p1], p2, epsilon) => {
+                        Segment::Line(p0, p2)
+                    }
+                    Segment::Cubic(p0, p1, p2, p3) if is_flat(p0, &[p1, p2], p3, epsilon) => {
+                        Segment::Line(p0, p3)
+                    }
+                    segment => segment,
+                };
+
+                // The start point of each segment is implied by the previous one, so
+                // dropped segments don't leave gaps.
+                if let Segment::Line(p0, p1) = segment {
+                    if p0.distance(p1) <= epsilon {
+                        continue;
+                    }
+
+                    if let Some(Segment::Line(a, b)) = segments.last().copied() {
+                        merged.push(b);
+                        if is_flat(a, &merged, p1, epsilon) {
+                            *segments.last_mut().unwrap() = Segment::Line(a, p1);
+                            continue;
+                        }
+                    }
+                }
+
+                merged.clear();
+                segments.push(segment);
+            }
+
+            let closed = c.is_closed();
+            c.set_segments(start, segments, closed);
+        }
+    }
+
+    /// Reduces the number of points in runs of lines with the Douglas-Peucker
+    /// algorithm.
+    ///
+    /// Points are removed as long as the lines stay within `epsilon` of the original
+    /// ones. Curves are left untouched, so use it after `flatten` to reduce the size
+    /// of polylines, e.g. for plotters.
+    pub fn decimate(&mut self, epsilon: f32) {
+        self.invalidate_bounds();
+        for c in &mut self.contours {
+            let start = match c.points.first() {
+                Some(p) => *p,
+                None => continue,
+            };
+
+            let closed = c.is_closed();
+            let mut segments: Vec<Segment> = Vec::new();
+            let mut run = vec![start];
+            // The implicit closing line takes part in the last run.
+            for segment in c
+                .segments()
+                .chain(if closed { c.closing_line() } else { None })
+            {
+                match segment {
+                    Segment::Line(_, p) => run.push(p),
+                    segment => {
+                        push_decimated(&run, epsilon, &mut segments);
+                        run.clear();
+                        run.push(segment.end());
+                        segments.push(segment);
+                    }
+                }
+            }
+
+            push_decimated(&run, epsilon, &mut segments);
+            if closed && matches!(segments.last(), Some(Segment::Line(_, p)) if *p == start) {
+                segments.pop();
+            }
+
+            c.set_segments(start, segments, closed);
+        }
+    }
+}
+
+// Adds the lines of a decimated polyline.
+fn push_decimated(points: &[Point], epsilon: f32, segments: &mut Vec<Segment>) {
+    if points.len() < 2 {
+        return;
+    }
+
+    let mut keep = vec![false; points.len()];
+    keep[0] = true;
+    keep[points.len() - 1] = true;
+    let mut stack = vec![(0, points.len() - 1)];
+    while let Some((first, last)) = stack.pop() {
+        let (a, b) = (points[first], points[last]);
+        let farthest = (first + 1..last)
+            .map(|i| (i, distance_to_line(points[i], a, b)))
+            .max_by(|x, y| x.1.total_cmp(&y.1));
+        if let Some((i, distance)) = farthest {
+            if distance > epsilon {
+                keep[i] = true;
+                stack.push((first, i));
+                stack.push((i, last));
+            }
+        }
+    }
+
+    let mut prev = points[0];
+    for (&p, _) in points.iter().zip(keep).skip(1).filter(|(_, keep)| *keep) {
+        segments.push(Segment::Line(prev, p));
+        prev = p;
+    }
+}
+
+// Returns the distance from `p` to the line segment from `a` to `b`.
+fn distance_to_line(p: Point, a: Point, b: Point) -> f32 {
+    let ab = b - a;
+    let len2 = ab.x * ab.x + ab.y * ab.y;
+    if len2 == 0.0 {
+        return p.distance(a);
+    }
+
+    let d = p - a;
+    let t = ((d.x * ab.x + d.y * ab.y) / len2).clamp(0.0, 1.0);
+    p.distance(a + ab * t)
+}
+
+// Returns `true` if the points are within `epsilon` of the line from `start` to `end`
+// and don't extend past its ends.
+fn is_flat(start: Point, points: &[Point], end: Point, epsilon: f32) -> bool {
+    let chord = end - start;
+    let len2 = chord.x * chord.x + chord.y * chord.y;
+    points.iter().all(|&p| {
+        let d = p - start;
+        if len2 == 0.0 {
+            return d.length() <= epsilon;
+        }
+
+        let t = (d.x * chord.x + d.y * chord.y) / len2;
+        let distance = (d.x * chord.y - d.y * chord.x).abs() / len2.sqrt();
+        (0.0..=1.0).contains(&t) && distance <= epsilon
+    })
+}
@@ -0,0 +1,36 @@
+use crate::Outline;
+
+impl Outline {
+    /// Returns the outline as a `tiny_skia::Path`.
+    ///
+    /// Returns `None` when the outline is empty, since `tiny-skia` has no empty paths.
+    pub fn to_tiny_skia_path(&self) -> Option<tiny_skia::Path> {
+        let mut builder = SkiaPathBuilder(tiny_skia::PathBuilder::new());
+        self.emit(&mut builder);
+        builder.0.finish()
+    }
+}
+
+struct SkiaPathBuilder(tiny_skia::PathBuilder);
+
+impl ttf_parser::OutlineBuilder for SkiaPathBuilder {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.0.move_to(x, y);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.0.line_to(x, y);
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        self.0.quad_to(x1, y1, x, y);
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        self.0.cubic_to(x1, y1, x2, y2, x, y);
+    }
+
+    fn close(&mut self) {
+        self.0.close();
+    }
+}
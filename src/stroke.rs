@@ -0,0 +1,328 @@
+use std::f32::consts::PI;
+
+use crate::{Contour, Outline, PathVerb, Point};
+
+/// The shape at the ends of open contours when stroking.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LineCap {
+    /// The stroke ends exactly at the end point.
+    Butt,
+    /// The stroke ends with a half circle.
+    Round,
+    /// The stroke extends past the end point by half the width.
+    Square,
+}
+
+/// The shape at the corners of contours when stroking.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LineJoin {
+    /// The outer edges are extended until they meet, up to the miter limit.
+    Miter,
+    /// The corner is rounded.
+    Round,
+    /// The corner is cut off.
+    Bevel,
+}
+
+impl Outline {
+    /// Returns an outline that fills the stroke of this outline.
+    ///
+    /// Sharp corners fall back to bevel joins when the miter length exceeds
+    /// `miter_limit` times the width. Curves are flattened within a hundredth of the
+    /// width, but at least a ten-thousandth of the outline size, and overlaps are
+    /// removed so the result fills the same under both fill rules.
+    pub fn stroke(&self, width: f32, cap: LineCap, join: LineJoin, miter_limit: f32) -> Outline {
+        let mut outline = Outline::empty(self.cff);
+        let half_width = width.abs() * 0.5;
+        if half_width == 0.0 {
+            return outline;
+        }
+
+        let tolerance = self.flatten_tolerance(half_width * 2.0);
+        let stroker = Stroker {
+            half_width,
+            join,
+            miter_limit,
+            tolerance,
+        };
+        for c in &self.contours {
+            let mut points = c.flatten_points(tolerance);
+            points.dedup();
+            while c.is_closed() && points.len() > 1 && points.first() == points.last() {
+                points.pop();
+            }
+
+            let mut polygons = Vec::new();
+            if points.len() == 1 {
+                // Only caps are visible for a single point.
+                let p = points[0];
+                let mut polygon = Vec::new();
+                if stroker.cap(p, Point::new(1.0, 0.0), cap, &mut polygon) {
+                    stroker.cap(p, Point::new(-1.0, 0.0), cap, &mut polygon);
+                    polygons.push(polygon);
+                }
+            } else if c.is_closed() {
+                polygons.push(stroker.offset(&points, true));
+                points.reverse();
+                polygons.push(stroker.offset(&points, true));
+            } else if points.len() > 1 {
+                let mut polygon = stroker.offset(&points, false);
+                let n = points.len();
+                stroker.cap(
+                    points[n - 1],
+                    direction(points[n - 2], points[n - 1]),
+                    cap,
+                    &mut polygon,
+                );
+                points.reverse();
+                polygon.extend(stroker.offset(&points, false));
+                stroker.cap(
+                    points[n - 1],
+                    direction(points[n - 2], points[n - 1]),
+                    cap,
+                    &mut polygon,
+                );
+                polygons.push(polygon);
+            }
+
+            // Make every contour fill with the same sign so they don't cancel out.
+            let area: f32 = polygons.iter().map(|p| polygon_area(p)).sum();
+            for mut points in polygons {
+                if area < 0.0 {
+                    points.reverse();
+                }
+
+                outline.push_polygon(points);
+            }
+        }
+
+        outline.remove_overlaps(tolerance);
+        outline
+    }
+
+    /// Returns an outline with all contours moved outwards by `distance`.
+    ///
+    /// A negative distance moves the contours inwards, and parts thinner than twice
+    /// the distance vanish. Corners that move outwards are rounded, which makes this
+    /// a true offset rather than the approximation of `embolden`. Curves are flattened
+    /// within a hundredth of the distance, but at least a ten-thousandth of the
+    /// outline size.
+    pub fn offset(&self, distance: f32) -> Outline {
+        let mut outline = Outline::empty(self.cff);
+        if distance == 0.0 {
+            outline.contours = self.contours.clone();
+            return outline;
+        }
+
+        let tolerance = self.flatten_tolerance(distance.abs());
+        let stroker = Stroker {
+            half_width: distance.abs(),
+            join: LineJoin::Round,
+            miter_limit: 0.0,
+            tolerance,
+        };
+        // Make the filled areas wind positively regardless of the outline format.
+        let sign = if self.contours.iter().map(|c| c.signed_area()).sum::<f32>() < 0.0 {
+            -1.0
+        } else {
+            1.0
+        };
+        // Holes run in the opposite direction, so the filled area is on the same side of
+        // every contour, i.e. to the left of them when winding positively. Offsetting
+        // works to the left, so contours are reversed to grow to the right.
+        let reverse = (sign > 0.0) == (distance > 0.0);
+        for c in &self.contours {
+            let mut points = c.flatten_points(tolerance);
+            points.dedup();
+            while points.len() > 1 && points.first() == points.last() {
+                points.pop();
+            }
+
+            if points.len() < 3 {
+                continue;
+            }
+
+            if reverse {
+                points.reverse();
+            }
+
+            let mut polygon = stroker.offset(&points, true);
+            if reverse {
+                polygon.reverse();
+            }
+
+            outline.push_polygon(polygon);
+        }
+
+        // Corners and parts that collapse wind the wrong way and are dropped.
+        if sign > 0.0 {
+            outline.remove_overlaps_with(tolerance, |winding| winding > 0);
+        } else {
+            outline.remove_overlaps_with(tolerance, |winding| winding < 0);
+        }
+
+        outline
+    }
+
+    pub(crate) fn flatten_tolerance(&self, size: f32) -> f32 {
+        let bbox = self.control_box();
+        (size * 0.01).max(bbox.width().max(bbox.height()) * 1e-4)
+    }
+
+    /// Adds a closed contour of lines.
+    pub(crate) fn push_polygon(&mut self, points: Vec<Point>) {
+        if points.is_empty() {
+            return;
+        }
+
+        let mut verbs = Vec::with_capacity(points.len() + 1);
+        verbs.push(PathVerb::MoveTo);
+        verbs.resize(points.len(), PathVerb::LineTo);
+        verbs.push(PathVerb::Close);
+        self.invalidate_bounds();
+        self.contours.push(Contour {
+            verbs,
+            points,
+            source_index: self.contours.len(),
+        });
+    }
+}
+
+struct Stroker {
+    half_width: f32,
+    join: LineJoin,
+    miter_limit: f32,
+    tolerance: f32,
+}
+
+impl Stroker {
+    /// Returns the polyline offset by the half width to its left, in a y-up
+    /// coordinate system.
+    ///
+    /// Closed polylines are joined at every point, open ones only at the inner points.
+    fn offset(&self, points: &[Point], closed: bool) -> Vec<Point> {
+        let n = points.len();
+        let mut out = Vec::with_capacity(n * 2);
+        if !closed {
+            let d = direction(points[0], points[1]);
+            out.push(points[0] + normal(d) * self.half_width);
+        }
+
+        let range = if closed { 0..n } else { 1..n - 1 };
+        for i in range {
+            let prev = points[(i + n - 1) % n];
+            let p = points[i];
+            let next = points[(i + 1) % n];
+            self.join(p, direction(prev, p), direction(p, next), &mut out);
+        }
+
+        if !closed {
+            let d = direction(points[n - 2], points[n - 1]);
+            out.push(points[n - 1] + normal(d) * self.half_width);
+        }
+
+        out
+    }
+
+    fn join(&self, p: Point, d0: Point, d1: Point, out: &mut Vec<Point>) {
+        let n0 = normal(d0);
+        let n1 = normal(d1);
+        let cross = d0.x * d1.y - d0.y * d1.x;
+        let dot = d0.x * d1.x + d0.y * d1.y;
+        let start = p + n0 * self.half_width;
+        let end = p + n1 * self.half_width;
+        if cross.abs() < 1e-6 && dot > 0.0 {
+            // Straight continuation.
+            out.push(start);
+            return;
+        }
+
+        if cross > 0.0 {
+            // The left side is on the inside of a left turn. Going through the point
+            // itself keeps the fill correct under the non-zero rule.
+            out.push(start);
+            out.push(p);
+            out.push(end);
+            return;
+        }
+
+        out.push(start);
+        match self.join {
+            LineJoin::Miter => {
+                // The miter length relative to the width is `1 / cos(theta / 2)`.
+                let bisector = n0 + n1;
+                let cos_half = bisector.length() * 0.5;
+                if cos_half > 0.0 && 1.0 / cos_half <= self.miter_limit {
+                    out.push(p + bisector * (self.half_width / (2.0 * cos_half * cos_half)));
+                }
+            }
+            LineJoin::Round => {
+                let sweep = (-cross).atan2(dot).abs();
+                self.arc(p, n0, sweep, out);
+            }
+            LineJoin::Bevel => {}
+        }
+
+        out.push(end);
+    }
+
+    /// Adds the cap at `p` for a polyline ending in direction `d`, going from the left
+    /// to the right side. Returns `false` if the cap has no area.
+    fn cap(&self, p: Point, d: Point, cap: LineCap, out: &mut Vec<Point>) -> bool {
+        let n = normal(d);
+        match cap {
+            LineCap::Butt => false,
+            LineCap::Round => {
+                out.push(p + n * self.half_width);
+                self.arc(p, n, PI, out);
+                out.push(p - n * self.half_width);
+                true
+            }
+            LineCap::Square => {
+                out.push(p + (n + d) * self.half_width);
+                out.push(p + (d - n) * self.half_width);
+                true
+            }
+        }
+    }
+
+    // Adds the inner points of a clockwise arc around `center`, starting at the unit
+    // vector `from` and sweeping by `sweep` radians.
+    fn arc(&self, center: Point, from: Point, sweep: f32, out: &mut Vec<Point>) {
+        // The largest step that keeps the chords within tolerance of the arc.
+        let step = 2.0 * (1.0 - (self.tolerance / self.half_width).min(1.0)).acos();
+        let steps = (sweep / step.max(1e-3)).ceil().max(1.0) as usize;
+        let angle = sweep / steps as f32;
+        for i in 1..steps {
+            let (sin, cos) = (-angle * i as f32).sin_cos();
+            let v = Point::new(from.x * cos - from.y * sin, from.x * sin + from.y * cos);
+            out.push(center + v * self.half_width);
+        }
+    }
+}
+
+#[inline]
+fn direction(from: Point, to: Point) -> Point {
+    let d = to - from;
+    d * (1.0 / d.length())
+}
+
+// Returns the normal to the left of a direction in a y-up coordinate system.
+#[inline]
+fn normal(d: Point) -> Point {
+    Point::new(-d.y, d.x)
+}
+
+fn polygon_area(points: &[Point]) -> f32 {
+    let n = points.len();
+    (0..n)
+        .map(|i| {
+            let p0 = points[i];
+            let p1 = points[(i + 1) % n];
+            p0.x * p1.y - p1.x * p0.y
+        })
+        .sum::<f32>()
+        * 0.5
+}
@@ -0,0 +1,97 @@
+use ttf_parser::{Face, GlyphId, Tag};
+
+use crate::otl::{coverage_index, feature_lookups, Lookup};
+use crate::parser::{read_i16, read_u16};
+use crate::Outline;
+
+const SINGLE: u16 = 1;
+const ALTERNATE: u16 = 3;
+const EXTENSION: u16 = 7;
+
+/// One-to-one glyph substitution with features of the `GSUB` table, like `smcp`,
+/// `onum` or `ss01`.
+///
+/// Only single and alternate substitution lookups are applied, where the first
+/// alternate is used. Substitutions that involve several glyphs, like ligatures, are
+/// left out.
+pub struct SingleSubstitution<'a> {
+    lookups: Vec<Lookup<'a>>,
+}
+
+impl<'a> SingleSubstitution<'a> {
+    /// Returns the substitution for the given features, which substitutes nothing
+    /// when the face has none of them.
+    pub fn new(face: &Face<'a>, features: &[Tag]) -> Self {
+        let lookups = face
+            .table_data(Tag::from_bytes(b"GSUB"))
+            .map(|data| feature_lookups(data, features, EXTENSION))
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|l| l.kind == SINGLE || l.kind == ALTERNATE)
+            .collect();
+        SingleSubstitution { lookups }
+    }
+
+    /// Returns `true` if the substitution has no lookups to apply.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.lookups.is_empty()
+    }
+
+    /// Returns the glyph substituted for `glyph_id`, which is the glyph itself when
+    /// no lookup applies.
+    ///
+    /// Lookups are applied in the order of the `GSUB` lookup list, each one to the
+    /// result of the previous.
+    pub fn apply(&self, glyph_id: GlyphId) -> GlyphId {
+        self.lookups.iter().fold(glyph_id, |glyph_id, lookup| {
+            lookup
+                .subtables
+                .iter()
+                .find_map(|data| substitute(data, lookup.kind, glyph_id))
+                .unwrap_or(glyph_id)
+        })
+    }
+}
+
+impl std::fmt::Debug for SingleSubstitution<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("SingleSubstitution")
+            .field("num_lookups", &self.lookups.len())
+            .finish()
+    }
+}
+
+impl Outline {
+    /// Returns a new outline for the glyph substituted by the given `GSUB` features
+    /// or `None` when that glyph has no outline or on error.
+    ///
+    /// See `SingleSubstitution` for which substitutions are applied, and use it
+    /// directly to look up the features once for many glyphs.
+    pub fn new_with_features(face: &Face, glyph_id: GlyphId, features: &[Tag]) -> Option<Self> {
+        Self::new(
+            face,
+            SingleSubstitution::new(face, features).apply(glyph_id),
+        )
+    }
+}
+
+fn substitute(data: &[u8], kind: u16, glyph_id: GlyphId) -> Option<GlyphId> {
+    let coverage = data.get(read_u16(data, 2)? as usize..)?;
+    let index = coverage_index(coverage, glyph_id)? as usize;
+    let glyph = match (kind, read_u16(data, 0)?) {
+        (SINGLE, 1) => glyph_id.0.wrapping_add(read_i16(data, 4)? as u16),
+        (SINGLE, 2) => read_u16(data, 6 + index * 2)?,
+        (ALTERNATE, 1) => {
+            let offset = read_u16(data, 6 + index * 2)? as usize;
+            if read_u16(data, offset)? == 0 {
+                return None;
+            }
+
+            read_u16(data, offset + 2)?
+        }
+        _ => return None,
+    };
+
+    Some(GlyphId(glyph))
+}
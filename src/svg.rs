@@ -0,0 +1,386 @@
+use ttf_parser::OutlineBuilder as _;
+
+use crate::{write_number, Outline, OutlineRecorder, Point, Transform};
+
+impl Outline {
+    /// Returns the outline as SVG path data.
+    pub fn to_svg_path(&self) -> String {
+        let mut writer = SvgPathWriter::new(None);
+        self.emit(&mut writer);
+        writer.finish()
+    }
+
+    /// Returns the outline as SVG path data with coordinates rounded to
+    /// `precision` decimal places.
+    pub fn to_svg_path_with_precision(&self, precision: usize) -> String {
+        let mut writer = SvgPathWriter::new(Some(precision));
+        self.emit(&mut writer);
+        writer.finish()
+    }
+
+    /// Returns a standalone SVG document containing the outline.
+    ///
+    /// The y-axis is flipped and the `viewBox` is derived from the ink box, so
+    /// the glyph is displayed upright and tightly framed.
+    pub fn to_svg_document(&self) -> String {
+        let bbox = self.ink_box();
+        let mut writer = SvgPathWriter::new(None);
+        self.emit_transformed(Transform::from_scale(1.0, -1.0), &mut writer);
+
+        let mut view_box = String::new();
+        for (i, n) in [bbox.x_min, -bbox.y_max, bbox.width(), bbox.height()]
+            .iter()
+            .enumerate()
+        {
+            if i > 0 {
+                view_box.push(' ');
+            }
+
+            write_number(&mut view_box, *n, None);
+        }
+
+        format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{}\"><path d=\"{}\"/></svg>",
+            view_box,
+            writer.finish()
+        )
+    }
+}
+
+struct SvgPathWriter {
+    data: String,
+    precision: Option<usize>,
+}
+
+impl SvgPathWriter {
+    #[inline]
+    fn new(precision: Option<usize>) -> Self {
+        Self {
+            data: String::new(),
+            precision,
+        }
+    }
+
+    #[inline]
+    fn finish(self) -> String {
+        self.data
+    }
+
+    fn push(&mut self, cmd: char, coords: &[f32]) {
+        if !self.data.is_empty() {
+            self.data.push(' ');
+        }
+
+        self.data.push(cmd);
+        for (i, n) in coords.iter().enumerate() {
+            if i > 0 {
+                self.data.push(' ');
+            }
+
+            write_number(&mut self.data, *n, self.precision);
+        }
+    }
+}
+
+impl ttf_parser::OutlineBuilder for SvgPathWriter {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.push('M', &[x, y]);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.push('L', &[x, y]);
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        self.push('Q', &[x1, y1, x, y]);
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        self.push('C', &[x1, y1, x2, y2, x, y]);
+    }
+
+    fn close(&mut self) {
+        self.push('Z', &[]);
+    }
+}
+
+impl Outline {
+    /// Parses an outline from SVG path data.
+    ///
+    /// All path commands are supported, in both absolute and relative form. Elliptical
+    /// arcs are approximated with cubic curves. Returns `None` on malformed data.
+    pub fn from_svg_path(data: &str) -> Option<Self> {
+        let mut builder = OutlineRecorder::new(false);
+        let mut parser = SvgPathParser {
+            data: data.as_bytes(),
+            pos: 0,
+        };
+
+        let mut cmd = b'M';
+        let mut first = true;
+        let mut current = Point::default();
+        let mut start = Point::default();
+        // The last control point, used to reflect smooth curves.
+        let mut last_ctrl: Option<(u8, Point)> = None;
+        let mut needs_move = false;
+        while parser.skip_separators() {
+            let c = parser.data[parser.pos];
+            if c.is_ascii_alphabetic() {
+                cmd = c;
+                parser.pos += 1;
+            } else if first || cmd.eq_ignore_ascii_case(&b'z') {
+                // Numbers must follow a command.
+                return None;
+            }
+
+            first = false;
+            let relative = cmd.is_ascii_lowercase();
+            let origin = if relative { current } else { Point::default() };
+            let upper = cmd.to_ascii_uppercase();
+            if upper != b'M' && upper != b'Z' && needs_move {
+                builder.move_to(start.x, start.y);
+                needs_move = false;
+            }
+
+            match upper {
+                b'M' => {
+                    let p = origin + parser.point()?;
+                    builder.move_to(p.x, p.y);
+                    current = p;
+                    start = p;
+                    needs_move = false;
+                    // Subsequent coordinate pairs are implicit line commands.
+                    cmd = if relative { b'l' } else { b'L' };
+                }
+                b'L' => {
+                    current = origin + parser.point()?;
+                    builder.line_to(current.x, current.y);
+                }
+                b'H' => {
+                    current.x = origin.x + parser.number()?;
+                    builder.line_to(current.x, current.y);
+                }
+                b'V' => {
+                    current.y = origin.y + parser.number()?;
+                    builder.line_to(current.x, current.y);
+                }
+                b'Q' | b'T' => {
+                    let p1 = if upper == b'Q' {
+                        origin + parser.point()?
+                    } else {
+                        reflect(last_ctrl, b'Q', current)
+                    };
+                    let p = origin + parser.point()?;
+                    builder.quad_to(p1.x, p1.y, p.x, p.y);
+                    current = p;
+                    last_ctrl = Some((b'Q', p1));
+                    continue;
+                }
+                b'C' | b'S' => {
+                    let p1 = if upper == b'C' {
+                        origin + parser.point()?
+                    } else {
+                        reflect(last_ctrl, b'C', current)
+                    };
+                    let p2 = origin + parser.point()?;
+                    let p = origin + parser.point()?;
+                    builder.curve_to(p1.x, p1.y, p2.x, p2.y, p.x, p.y);
+                    current = p;
+                    last_ctrl = Some((b'C', p2));
+                    continue;
+                }
+                b'A' => {
+                    let rx = parser.number()?;
+                    let ry = parser.number()?;
+                    let rotation = parser.number()?;
+                    let large_arc = parser.flag()?;
+                    let sweep = parser.flag()?;
+                    let p = origin + parser.point()?;
+                    arc_to(&mut builder, current, rx, ry, rotation, large_arc, sweep, p);
+                    current = p;
+                }
+                b'Z' => {
+                    builder.close();
+                    current = start;
+                    needs_move = true;
+                }
+                _ => return None,
+            }
+
+            last_ctrl = None;
+        }
+
+        Some(builder.finish())
+    }
+}
+
+#[inline]
+fn reflect(last_ctrl: Option<(u8, Point)>, cmd: u8, current: Point) -> Point {
+    match last_ctrl {
+        Some((c, p)) if c == cmd => current * 2.0 - p,
+        _ => current,
+    }
+}
+
+// Approximates an SVG elliptical arc with cubic curves as described in the SVG
+// implementation notes.
+#[allow(clippy::too_many_arguments)]
+fn arc_to(
+    builder: &mut OutlineRecorder,
+    from: Point,
+    rx: f32,
+    ry: f32,
+    rotation: f32,
+    large_arc: bool,
+    sweep: bool,
+    to: Point,
+) {
+    if from == to {
+        return;
+    }
+
+    let mut rx = rx.abs();
+    let mut ry = ry.abs();
+    if rx == 0.0 || ry == 0.0 {
+        builder.line_to(to.x, to.y);
+        return;
+    }
+
+    let (sin_phi, cos_phi) = rotation.to_radians().sin_cos();
+    let dx = (from.x - to.x) * 0.5;
+    let dy = (from.y - to.y) * 0.5;
+    let x1 = cos_phi * dx + sin_phi * dy;
+    let y1 = -sin_phi * dx + cos_phi * dy;
+
+    // Scale up the radii when they are too small to reach the end point.
+    let lambda = (x1 * x1) / (rx * rx) + (y1 * y1) / (ry * ry);
+    if lambda > 1.0 {
+        let s = lambda.sqrt();
+        rx *= s;
+        ry *= s;
+    }
+
+    let num = rx * rx * ry * ry - rx * rx * y1 * y1 - ry * ry * x1 * x1;
+    let den = rx * rx * y1 * y1 + ry * ry * x1 * x1;
+    let mut coef = (num / den).max(0.0).sqrt();
+    if large_arc == sweep {
+        coef = -coef;
+    }
+
+    let cx1 = coef * rx * y1 / ry;
+    let cy1 = -coef * ry * x1 / rx;
+    let cx = cos_phi * cx1 - sin_phi * cy1 + (from.x + to.x) * 0.5;
+    let cy = sin_phi * cx1 + cos_phi * cy1 + (from.y + to.y) * 0.5;
+
+    let angle = |ux: f32, uy: f32, vx: f32, vy: f32| (ux * vy - uy * vx).atan2(ux * vx + uy * vy);
+    let theta1 = angle(1.0, 0.0, (x1 - cx1) / rx, (y1 - cy1) / ry);
+    let mut delta = angle(
+        (x1 - cx1) / rx,
+        (y1 - cy1) / ry,
+        (-x1 - cx1) / rx,
+        (-y1 - cy1) / ry,
+    );
+    if !sweep && delta > 0.0 {
+        delta -= 2.0 * std::f32::consts::PI;
+    } else if sweep && delta < 0.0 {
+        delta += 2.0 * std::f32::consts::PI;
+    }
+
+    // Use at most a quarter turn per cubic curve.
+    let num_segments = (delta.abs() / std::f32::consts::FRAC_PI_2).ceil().max(1.0) as usize;
+    let step = delta / num_segments as f32;
+    let k = 4.0 / 3.0 * (step / 4.0).tan();
+    let map = |x: f32, y: f32| {
+        Point::new(
+            cos_phi * rx * x - sin_phi * ry * y + cx,
+            sin_phi * rx * x + cos_phi * ry * y + cy,
+        )
+    };
+    let mut theta = theta1;
+    for i in 0..num_segments {
+        let (sin1, cos1) = theta.sin_cos();
+        let (sin2, cos2) = (theta + step).sin_cos();
+        let p1 = map(cos1 - k * sin1, sin1 + k * cos1);
+        let p2 = map(cos2 + k * sin2, sin2 - k * cos2);
+        // Snap the last point to avoid accumulated floating point errors.
+        let p = if i + 1 == num_segments {
+            to
+        } else {
+            map(cos2, sin2)
+        };
+        builder.curve_to(p1.x, p1.y, p2.x, p2.y, p.x, p.y);
+        theta += step;
+    }
+}
+
+struct SvgPathParser<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl SvgPathParser<'_> {
+    /// Skips whitespace and commas and returns `false` at the end of the data.
+    fn skip_separators(&mut self) -> bool {
+        while let Some(c) = self.data.get(self.pos) {
+            if c.is_ascii_whitespace() || *c == b',' {
+                self.pos += 1;
+            } else {
+                return true;
+            }
+        }
+
+        false
+    }
+
+    fn number(&mut self) -> Option<f32> {
+        self.skip_separators();
+        let start = self.pos;
+        if let Some(b'+') | Some(b'-') = self.data.get(self.pos) {
+            self.pos += 1;
+        }
+
+        self.skip_digits();
+        if self.data.get(self.pos) == Some(&b'.') {
+            self.pos += 1;
+            self.skip_digits();
+        }
+
+        if let Some(b'e') | Some(b'E') = self.data.get(self.pos) {
+            // Don't mistake the start of a following command for an exponent.
+            if let Some(c) = self.data.get(self.pos + 1) {
+                if c.is_ascii_digit() || *c == b'+' || *c == b'-' {
+                    self.pos += 2;
+                    self.skip_digits();
+                }
+            }
+        }
+
+        std::str::from_utf8(&self.data[start..self.pos])
+            .ok()?
+            .parse()
+            .ok()
+    }
+
+    fn point(&mut self) -> Option<Point> {
+        Some(Point::new(self.number()?, self.number()?))
+    }
+
+    /// Parses an arc flag, which doesn't need to be followed by a separator.
+    fn flag(&mut self) -> Option<bool> {
+        self.skip_separators();
+        let flag = match self.data.get(self.pos)? {
+            b'0' => false,
+            b'1' => true,
+            _ => return None,
+        };
+        self.pos += 1;
+        Some(flag)
+    }
+
+    #[inline]
+    fn skip_digits(&mut self) {
+        while matches!(self.data.get(self.pos), Some(c) if c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+    }
+}
@@ -0,0 +1,195 @@
+use crate::{Outline, Transform};
+
+// The heights, relative to the ink box, where stems are measured.
+const STEM_HEIGHTS: [f32; 3] = [0.3, 0.5, 0.7];
+
+/// The height that stays in place when slanting an outline.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ObliquePivot {
+    /// The baseline, like `Outline::oblique`.
+    Baseline,
+    /// Half of the given x-height, e.g. from `Face::x_height`.
+    XHeightCenter(f32),
+    /// The vertical center of the control box.
+    BBoxCenter,
+}
+
+/// The position of a synthetic superscript or subscript glyph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ScriptPosition {
+    /// Above the baseline, e.g. for footnote markers.
+    Superscript,
+    /// Below the baseline, e.g. for chemical formulas.
+    Subscript,
+}
+
+// Fallbacks relative to the em when the `OS/2` table has no usable values.
+const SCRIPT_SIZE: f32 = 0.65;
+const SUPERSCRIPT_OFFSET: f32 = 0.45;
+const SUBSCRIPT_OFFSET: f32 = 0.14;
+
+// The `.notdef` box relative to the em, leaving room for an advance of 0.6 em.
+const NOTDEF_LEFT: f32 = 0.05;
+const NOTDEF_WIDTH: f32 = 0.5;
+const NOTDEF_HEIGHT: f32 = 0.7;
+const NOTDEF_STROKE: f32 = 0.05;
+
+impl Outline {
+    /// Returns a hollow rectangle like the usual `.notdef` glyph.
+    ///
+    /// The box sits on the baseline and is sized from the em, so it fits an advance
+    /// width of 0.6 em. Use it when a face lacks a glyph or its outline.
+    pub fn notdef_box(units_per_em: u16) -> Self {
+        let em = units_per_em as f32;
+        let (x0, y0) = (NOTDEF_LEFT * em, 0.0);
+        let (x1, y1) = (x0 + NOTDEF_WIDTH * em, NOTDEF_HEIGHT * em);
+        let stroke = NOTDEF_STROKE * em;
+
+        // Clockwise outside and counter-clockwise inside, like in `glyf` tables.
+        let mut outline = Outline::default();
+        outline.move_to(x0, y0);
+        outline.line_to(x0, y1);
+        outline.line_to(x1, y1);
+        outline.line_to(x1, y0);
+        outline.close();
+        outline.move_to(x0 + stroke, y0 + stroke);
+        outline.line_to(x1 - stroke, y0 + stroke);
+        outline.line_to(x1 - stroke, y1 - stroke);
+        outline.line_to(x0 + stroke, y1 - stroke);
+        outline.close();
+        outline
+    }
+
+    /// Returns a synthetic small cap for a glyph or `None` when it has no outline or
+    /// on error.
+    ///
+    /// The glyph, usually an uppercase letter, is scaled by `scale`, e.g. the
+    /// x-height divided by the cap height, and emboldened so its stems get
+    /// `stem_compensation` font units wider to make up for the scaling. Scale the
+    /// advance width by the same factor and add `stem_compensation` to it.
+    pub fn new_small_cap(
+        face: &ttf_parser::Face,
+        glyph_id: ttf_parser::GlyphId,
+        scale: f32,
+        stem_compensation: f32,
+    ) -> Option<Self> {
+        let mut outline = Self::new(face, glyph_id)?;
+        outline.scale(scale, scale);
+        if stem_compensation != 0.0 {
+            outline.embolden(stem_compensation * 0.5);
+        }
+
+        Some(outline)
+    }
+
+    /// Scales and moves the outline into a superscript or subscript position.
+    ///
+    /// The sizes and offsets come from the `OS/2` table of the face, falling back to
+    /// 65% of the em raised by 45% or lowered by 14% of the em when they are missing
+    /// or zero. Returns the horizontal scale, which should be applied to the advance
+    /// width as well.
+    pub fn to_script(&mut self, face: &ttf_parser::Face, position: ScriptPosition) -> f32 {
+        let upem = face.units_per_em().unwrap_or(1000) as f32;
+        let (metrics, offset) = match position {
+            ScriptPosition::Superscript => (face.superscript_metrics(), SUPERSCRIPT_OFFSET),
+            ScriptPosition::Subscript => (face.subscript_metrics(), -SUBSCRIPT_OFFSET),
+        };
+
+        let (sx, sy, dx, dy) = match metrics {
+            Some(m) if m.x_size > 0 && m.y_size > 0 => {
+                // Subscript offsets are positive downwards.
+                let dy = match position {
+                    ScriptPosition::Superscript => m.y_offset as f32,
+                    ScriptPosition::Subscript => -(m.y_offset as f32),
+                };
+                let dy = if dy == 0.0 { offset * upem } else { dy };
+                (
+                    m.x_size as f32 / upem,
+                    m.y_size as f32 / upem,
+                    m.x_offset as f32,
+                    dy,
+                )
+            }
+            _ => (SCRIPT_SIZE, SCRIPT_SIZE, 0.0, offset * upem),
+        };
+
+        self.transform(Transform::from_scale(sx, sy).then(&Transform::from_translate(dx, dy)));
+        sx
+    }
+
+    /// Scales the outline horizontally while keeping the weight of vertical stems.
+    ///
+    /// Factors below one condense the outline and above one extend it. The stem width
+    /// is estimated from the ink at a few heights, and the outline is emboldened or
+    /// thinned horizontally to bring the scaled stems back to it. Returns how much
+    /// this added to the width, on top of the scaling, which should be applied to the
+    /// advance width as well.
+    pub fn condense(&mut self, factor: f32) -> f32 {
+        let stem = self.stem_width();
+        self.scale(factor, 1.0);
+        let strength = match stem {
+            Some(stem) => stem * (1.0 - factor) * 0.5,
+            None => return 0.0,
+        };
+
+        if strength != 0.0 {
+            self.embolden_xy(strength, 0.0);
+        }
+
+        strength * 2.0
+    }
+
+    // Returns the median width of the horizontal ink runs at a few heights.
+    fn stem_width(&self) -> Option<f32> {
+        let bbox = self.ink_box();
+        let mut runs = Vec::new();
+        for &height in &STEM_HEIGHTS {
+            let y = bbox.y_min + bbox.height() * height;
+            let mut xs = Vec::new();
+            for c in &self.contours {
+                for segment in c.segments().chain(c.closing_line()) {
+                    segment.for_each_crossing(false, y, |t| xs.push(segment.eval(t).x));
+                }
+            }
+
+            // Crossings at segment ends are missed, which leaves an odd count.
+            if xs.len() % 2 != 0 {
+                continue;
+            }
+
+            xs.sort_by(|a, b| a.total_cmp(b));
+            runs.extend(xs.chunks(2).map(|pair| pair[1] - pair[0]));
+        }
+
+        runs.sort_by(|a, b| a.total_cmp(b));
+        runs.get(runs.len() / 2).copied()
+    }
+
+    /// Slant the outline by an angle in degrees around a pivot height.
+    ///
+    /// Positive angles lean to the right. Returns how far the right edge of the ink
+    /// box moved, which can be added to the advance width so slanted glyphs keep
+    /// their spacing.
+    pub fn oblique_at(&mut self, angle: f32, pivot: ObliquePivot) -> f32 {
+        let y = match pivot {
+            ObliquePivot::Baseline => 0.0,
+            ObliquePivot::XHeightCenter(x_height) => x_height * 0.5,
+            ObliquePivot::BBoxCenter => self.control_box().center().1,
+        };
+
+        let x_max = self.ink_box().x_max;
+        self.transform(
+            Transform::from_translate(0.0, -y)
+                .then(&Transform::from_skew(angle.to_radians().tan(), 0.0))
+                .then(&Transform::from_translate(0.0, y)),
+        );
+
+        if self.is_empty() {
+            0.0
+        } else {
+            self.ink_box().x_max - x_max
+        }
+    }
+}
@@ -0,0 +1,112 @@
+/// A 2D affine transform.
+///
+/// Maps a point `(x, y)` to `(a * x + c * y + e, b * x + d * y + f)`, which is
+/// the same layout as an SVG `matrix(a b c d e f)`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Transform {
+    /// Horizontal scale.
+    pub a: f32,
+    /// Vertical skew.
+    pub b: f32,
+    /// Horizontal skew.
+    pub c: f32,
+    /// Vertical scale.
+    pub d: f32,
+    /// Horizontal translation.
+    pub e: f32,
+    /// Vertical translation.
+    pub f: f32,
+}
+
+impl Transform {
+    /// Returns a new transform.
+    #[inline]
+    pub fn new(a: f32, b: f32, c: f32, d: f32, e: f32, f: f32) -> Self {
+        Self { a, b, c, d, e, f }
+    }
+
+    /// Returns the identity transform.
+    #[inline]
+    pub fn identity() -> Self {
+        Self::new(1.0, 0.0, 0.0, 1.0, 0.0, 0.0)
+    }
+
+    /// Returns a scale transform.
+    #[inline]
+    pub fn from_scale(sx: f32, sy: f32) -> Self {
+        Self::new(sx, 0.0, 0.0, sy, 0.0, 0.0)
+    }
+
+    /// Returns a translation transform.
+    #[inline]
+    pub fn from_translate(dx: f32, dy: f32) -> Self {
+        Self::new(1.0, 0.0, 0.0, 1.0, dx, dy)
+    }
+
+    /// Returns a skew transform.
+    ///
+    /// The factors are the tangents of the skew angles, i.e. the same kind of value
+    /// that is passed to `Outline::oblique`.
+    #[inline]
+    pub fn from_skew(kx: f32, ky: f32) -> Self {
+        Self::new(1.0, ky, kx, 1.0, 0.0, 0.0)
+    }
+
+    /// Returns a rotation transform.
+    ///
+    /// The angle is in radians, counter-clockwise in a y-up coordinate system.
+    #[inline]
+    pub fn from_rotate(angle: f32) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Self::new(cos, sin, -sin, cos, 0.0, 0.0)
+    }
+
+    /// Returns a rotation transform around the given pivot point.
+    pub fn from_rotate_at(angle: f32, cx: f32, cy: f32) -> Self {
+        Self::from_translate(-cx, -cy)
+            .then(&Self::from_rotate(angle))
+            .then(&Self::from_translate(cx, cy))
+    }
+
+    /// Returns `true` if this is the identity transform.
+    #[inline]
+    pub fn is_identity(&self) -> bool {
+        *self == Self::identity()
+    }
+
+    /// Returns a transform that applies `self` followed by `other`.
+    pub fn then(&self, other: &Transform) -> Self {
+        Self {
+            a: other.a * self.a + other.c * self.b,
+            b: other.b * self.a + other.d * self.b,
+            c: other.a * self.c + other.c * self.d,
+            d: other.b * self.c + other.d * self.d,
+            e: other.a * self.e + other.c * self.f + other.e,
+            f: other.b * self.e + other.d * self.f + other.f,
+        }
+    }
+
+    /// Applies the transform to a point.
+    #[inline]
+    pub fn apply(&self, x: f32, y: f32) -> (f32, f32) {
+        (
+            self.a * x + self.c * y + self.e,
+            self.b * x + self.d * y + self.f,
+        )
+    }
+}
+
+impl Default for Transform {
+    #[inline]
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl From<[f32; 6]> for Transform {
+    #[inline]
+    fn from(m: [f32; 6]) -> Self {
+        Self::new(m[0], m[1], m[2], m[3], m[4], m[5])
+    }
+}
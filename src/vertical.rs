@@ -0,0 +1,92 @@
+use ttf_parser::{Face, Tag};
+
+use crate::layout::{Context, Run};
+use crate::metrics::vertical_metrics;
+use crate::{Direction, Glyph, LayoutOptions, Outline, Transform};
+
+/// The orientation of a run of text in vertical layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VerticalOrientation {
+    /// Glyphs are upright and stacked, like CJK.
+    Upright,
+    /// Glyphs are turned 90° clockwise and follow each other like in horizontal text,
+    /// like Latin in vertical text.
+    Rotated,
+}
+
+/// Returns the glyphs of runs of text placed top to bottom, centered on a vertical
+/// line through the origin, see `outlines_for_str` for how glyphs are selected.
+///
+/// Upright glyphs advance by the vertical metrics of the `vhea` and `vmtx` tables,
+/// with their vertical origin from the `VORG` table or the vertical metrics. Faces
+/// without vertical metrics advance by the ascender minus the descender, with the
+/// origin at the ascender. The `vert` feature selects vertical forms, like rotated
+/// brackets, for upright runs.
+///
+/// Rotated runs are laid out like horizontal text, kerning included, and turned
+/// clockwise with the middle of the ascender and the descender on the line. Their
+/// outlines are rotated, but not positioned. The advance of every glyph is along the
+/// line, and the spacing of the options applies to both kinds of runs.
+pub fn outlines_for_vertical_runs(
+    face: &Face,
+    runs: &[(&str, VerticalOrientation)],
+    options: LayoutOptions,
+) -> Vec<Glyph> {
+    let mut upright_options = options.clone();
+    upright_options.features.push(Tag::from_bytes(b"vert"));
+    let upright = Context::new(face, upright_options);
+    let rotated = Context::new(face, options.clone());
+    // Rotated runs are centered on the line by the middle of the em box.
+    let center = (f32::from(face.ascender()) + f32::from(face.descender())) * 0.5;
+    let rotation = Transform::new(0.0, -1.0, 1.0, 0.0, 0.0, 0.0);
+
+    let mut glyphs = Vec::new();
+    let mut y = 0.0;
+    for &(text, orientation) in runs {
+        match orientation {
+            VerticalOrientation::Upright => {
+                for (id, c) in upright.glyph_ids(text).into_iter().zip(text.chars()) {
+                    let outline = Outline::new(face, id);
+                    let (mut advance, origin) = vertical_metrics(face, id, outline.as_ref());
+                    let width = face.glyph_hor_advance(id).map_or(0.0, f32::from);
+                    advance += options.letter_spacing;
+                    if c.is_whitespace() {
+                        advance += options.word_spacing;
+                    }
+
+                    glyphs.push(Glyph {
+                        id,
+                        outline,
+                        advance,
+                        offset: (-width * 0.5, y - origin),
+                        face_index: 0,
+                    });
+                    y -= advance;
+                }
+            }
+            VerticalOrientation::Rotated => {
+                let mut run_glyphs = Vec::new();
+                let mut x = 0.0;
+                let run = Run {
+                    text,
+                    direction: Direction::LeftToRight,
+                    y: 0.0,
+                };
+                rotated.push_run(run, &mut x, &mut run_glyphs);
+                for mut glyph in run_glyphs {
+                    if let Some(outline) = &mut glyph.outline {
+                        outline.transform(rotation);
+                    }
+
+                    glyph.offset = (glyph.offset.1 - center, y - glyph.offset.0);
+                    glyphs.push(glyph);
+                }
+
+                y -= x;
+            }
+        }
+    }
+
+    glyphs
+}
@@ -0,0 +1,170 @@
+use crate::geom::Segment;
+use crate::{BBox, Outline, Point};
+
+// The largest piece of a curve, relative to the envelope size, that is warped by
+// moving its control points.
+const MAX_PIECE: f32 = 1.0 / 32.0;
+
+/// A shape bounded by four cubic curves that an outline can be warped into.
+///
+/// Each edge is given by its start point, two control points and end point. The top
+/// and bottom edges run from left to right and the left and right edges from bottom
+/// to top in a y-up coordinate system, so the corners are shared between edges.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Envelope {
+    /// The top edge.
+    pub top: [(f32, f32); 4],
+    /// The right edge.
+    pub right: [(f32, f32); 4],
+    /// The bottom edge.
+    pub bottom: [(f32, f32); 4],
+    /// The left edge.
+    pub left: [(f32, f32); 4],
+}
+
+impl Envelope {
+    /// Returns an envelope with straight edges along the bbox, which doesn't change
+    /// the outline.
+    pub fn from_bbox(bbox: BBox) -> Self {
+        Self::with_bends(bbox, (0.0, 0.0), (0.0, 0.0))
+    }
+
+    /// Returns an envelope where the top and bottom edges bend upwards into arcs.
+    ///
+    /// The middle of the edges moves by `bend` times the bbox height, downwards when
+    /// it's negative.
+    pub fn arc(bbox: BBox, bend: f32) -> Self {
+        Self::with_bends(bbox, (bend, bend), (bend, bend))
+    }
+
+    /// Returns an envelope where the top and bottom edges form waves in opposite
+    /// directions, bulging on the left and pinching on the right.
+    ///
+    /// The edges move by up to about `bend` times the bbox height.
+    pub fn wave(bbox: BBox, bend: f32) -> Self {
+        Self::with_bends(bbox, (bend, -bend), (-bend, bend))
+    }
+
+    /// Returns an envelope where the top and bottom edges form the same wave, like a
+    /// flag in the wind.
+    ///
+    /// The edges move by up to about `bend` times the bbox height.
+    pub fn flag(bbox: BBox, bend: f32) -> Self {
+        Self::with_bends(bbox, (bend, -bend), (bend, -bend))
+    }
+
+    // Returns an envelope along the bbox with the control points of the top and bottom
+    // edges moved vertically, relative to the bbox height.
+    fn with_bends(bbox: BBox, top: (f32, f32), bottom: (f32, f32)) -> Self {
+        // The middle of a cubic curve moves by 3/4 of its control points.
+        let h = bbox.height() * 4.0 / 3.0;
+        let (x0, x1) = (bbox.x_min, bbox.x_max);
+        let (y0, y1) = (bbox.y_min, bbox.y_max);
+        let edge = |y: f32, (a, b): (f32, f32)| {
+            [
+                (x0, y),
+                (x0 + (x1 - x0) / 3.0, y + a * h),
+                (x1 - (x1 - x0) / 3.0, y + b * h),
+                (x1, y),
+            ]
+        };
+        let side = |x: f32| {
+            [
+                (x, y0),
+                (x, y0 + (y1 - y0) / 3.0),
+                (x, y1 - (y1 - y0) / 3.0),
+                (x, y1),
+            ]
+        };
+
+        Envelope {
+            top: edge(y1, top),
+            right: side(x1),
+            bottom: edge(y0, bottom),
+            left: side(x0),
+        }
+    }
+
+    // Maps a point in the unit square into the envelope as a Coons patch.
+    fn map(&self, u: f32, v: f32) -> Point {
+        let eval = |edge: &[(f32, f32); 4], t: f32| {
+            let p = |i: usize| Point::new(edge[i].0, edge[i].1);
+            Segment::Cubic(p(0), p(1), p(2), p(3)).eval(t)
+        };
+
+        let bottom = eval(&self.bottom, u);
+        let top = eval(&self.top, u);
+        let left = eval(&self.left, v);
+        let right = eval(&self.right, v);
+        let corner = |(x, y): (f32, f32)| Point::new(x, y);
+        let p00 = corner(self.bottom[0]);
+        let p10 = corner(self.bottom[3]);
+        let p01 = corner(self.top[0]);
+        let p11 = corner(self.top[3]);
+
+        bottom * (1.0 - v) + top * v + left * (1.0 - u) + right * u
+            - (p00 * ((1.0 - u) * (1.0 - v))
+                + p10 * (u * (1.0 - v))
+                + p01 * ((1.0 - u) * v)
+                + p11 * (u * v))
+    }
+}
+
+impl Outline {
+    /// Warps the outline from its control box into an envelope.
+    ///
+    /// Lines become cubic curves, and curves are split into pieces no larger than
+    /// 1/32 of the control box before their control points are warped, which keeps
+    /// the result close to the exact warp.
+    pub fn warp(&mut self, envelope: &Envelope) {
+        let bbox = self.control_box();
+        let (w, h) = (bbox.width(), bbox.height());
+        let unit = |p: Point| {
+            let u = if w > 0.0 { (p.x - bbox.x_min) / w } else { 0.0 };
+            let v = if h > 0.0 { (p.y - bbox.y_min) / h } else { 0.0 };
+            envelope.map(u, v)
+        };
+        let max_piece = w.max(h) * MAX_PIECE;
+
+        self.invalidate_bounds();
+        for c in &mut self.contours {
+            let start = match c.points.first() {
+                Some(p) => *p,
+                None => continue,
+            };
+
+            let closed = c.is_closed();
+            let closing_line = if closed { c.closing_line() } else { None };
+            let mut segments = Vec::new();
+            for segment in c.segments().chain(closing_line) {
+                let segment = match segment {
+                    Segment::Line(p0, p1) => {
+                        Segment::Cubic(p0, p0.lerp(p1, 1.0 / 3.0), p0.lerp(p1, 2.0 / 3.0), p1)
+                    }
+                    segment => segment,
+                };
+
+                let len = segment.arc_length(max_piece * 0.01);
+                let pieces = if max_piece > 0.0 {
+                    (len / max_piece).ceil().max(1.0) as usize
+                } else {
+                    1
+                };
+                for i in 0..pieces {
+                    let piece = segment
+                        .subsegment(i as f32 / pieces as f32, (i + 1) as f32 / pieces as f32);
+                    segments.push(match piece {
+                        Segment::Line(p0, p1) => Segment::Line(unit(p0), unit(p1)),
+                        Segment::Quad(p0, p1, p2) => Segment::Quad(unit(p0), unit(p1), unit(p2)),
+                        Segment::Cubic(p0, p1, p2, p3) => {
+                            Segment::Cubic(unit(p0), unit(p1), unit(p2), unit(p3))
+                        }
+                    });
+                }
+            }
+
+            c.set_segments(unit(start), segments, closed);
+        }
+    }
+}